@@ -0,0 +1,44 @@
+use ovh::client::OvhClient;
+use ovh::schema::{CodeGenerator, OvhApiSchema};
+
+use clap::Clap;
+
+/// Generates Rust bindings for a section of OVH's REST API from its schema
+#[derive(Clap)]
+struct Opts {
+    /// File containing API credentials
+    #[clap(short, long, default_value = "ovh.conf")]
+    config: String,
+
+    /// Section of the API to generate, such as /domain
+    section: String,
+
+    /// Only generate the routes under this path, may be repeated
+    #[clap(short, long)]
+    prefix: Vec<String>,
+
+    /// File to write the module to, instead of the standard output
+    #[clap(short, long)]
+    output: Option<String>,
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let opts: Opts = Opts::parse();
+
+    let c = OvhClient::from_conf(&opts.config)?;
+    let schema = OvhApiSchema::get(&c, &opts.section).await?;
+
+    let mut generator = CodeGenerator::new();
+    for p in &opts.prefix {
+        generator = generator.with_prefix(p);
+    }
+    let code = generator.generate(&schema);
+
+    match opts.output {
+        Some(path) => std::fs::write(path, code)?,
+        None => print!("{}", code),
+    }
+
+    Ok(())
+}
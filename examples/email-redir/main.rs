@@ -1,5 +1,5 @@
 use ovh::client::OvhClient;
-use ovh::email_redir::OvhMailRedir;
+use ovh::email_redir::{OvhMailRedir, RedirId};
 
 use clap::Clap;
 
@@ -40,7 +40,7 @@ struct CreateArgs {
 struct DeleteArgs {
     domain: String,
 
-    id: String,
+    id: RedirId,
 }
 
 #[derive(Clap)]
@@ -56,7 +56,7 @@ enum SubCommand {
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let opts: Opts = Opts::parse();
 
     let c = OvhClient::from_conf(&opts.config)?;
@@ -74,7 +74,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("{:#?}", resp.text().await?);
         }
         SubCommand::Delete(a) => {
-            let resp = OvhMailRedir::delete(&c, &a.domain, &a.id).await?;
+            let resp = OvhMailRedir::delete(&c, &a.domain, a.id).await?;
             println!("{:#?}", resp);
         }
     }
@@ -0,0 +1,91 @@
+use std::collections::HashMap;
+
+use ovh::client::OvhClient;
+use ovh::secret::{SecretProvider, SopsProvider};
+
+use clap::Clap;
+use serde::Deserialize;
+
+/// Builds an OVH client from credentials stored in HashiCorp Vault or in a
+/// SOPS-encrypted file, then prints the account nichandle
+#[derive(Clap)]
+struct Opts {
+    #[clap(subcommand)]
+    subcmd: SubCommand,
+}
+
+#[derive(Clap)]
+struct VaultArgs {
+    /// Address of the Vault server
+    #[clap(long, default_value = "http://127.0.0.1:8200")]
+    addr: String,
+
+    /// Mount point of the KV version 2 secrets engine
+    #[clap(long, default_value = "secret")]
+    mount: String,
+
+    /// Path of the secret holding the credentials
+    path: String,
+}
+
+#[derive(Clap)]
+struct SopsArgs {
+    /// SOPS-encrypted file holding the credentials
+    file: String,
+}
+
+#[derive(Clap)]
+enum SubCommand {
+    /// Read credentials from Vault, authenticating with $VAULT_TOKEN
+    Vault(VaultArgs),
+
+    /// Read credentials from a SOPS-encrypted file
+    Sops(SopsArgs),
+}
+
+#[derive(Deserialize)]
+struct VaultResponse {
+    data: VaultData,
+}
+
+#[derive(Deserialize)]
+struct VaultData {
+    data: HashMap<String, String>,
+}
+
+/// Reads a secret from the KV version 2 secrets engine of Vault.
+async fn read_vault(
+    args: &VaultArgs,
+    token: &str,
+) -> Result<HashMap<String, String>, Box<dyn std::error::Error + Send + Sync>> {
+    let url = format!("{}/v1/{}/data/{}", args.addr, args.mount, args.path);
+    let resp: VaultResponse = reqwest::Client::new()
+        .get(url)
+        .header("X-Vault-Token", token)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(resp.data.data)
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let opts: Opts = Opts::parse();
+
+    let provider: Box<dyn SecretProvider> = match opts.subcmd {
+        SubCommand::Vault(a) => {
+            let token = std::env::var("VAULT_TOKEN")?;
+            Box::new(read_vault(&a, &token).await?)
+        }
+        SubCommand::Sops(a) => Box::new(SopsProvider::load(a.file)?),
+    };
+
+    let c = OvhClient::from_provider(provider.as_ref())?;
+
+    let me: serde_json::Value = c.get("/me").await?.error_for_status()?.json().await?;
+    println!("{}", me["nichandle"]);
+
+    Ok(())
+}
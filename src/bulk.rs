@@ -0,0 +1,259 @@
+//! Resumable execution of bulk operations.
+//!
+//! Big jobs such as importing thousands of DNS records can take longer
+//! than a maintenance window, or get interrupted. [`BulkExecutor`] runs an
+//! operation for each item of a job, records every item done in a
+//! [`CheckpointStore`], and skips them when the job is run again.
+
+use std::{
+    collections::HashSet,
+    fs::{File, OpenOptions},
+    future::Future,
+    io::{BufRead, BufReader, ErrorKind, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use futures::StreamExt;
+
+/// Storage of the identifiers of the items already processed.
+pub trait CheckpointStore {
+    /// Retrieves the identifiers of the items already processed.
+    fn load(&self) -> Result<HashSet<String>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Records that an item has been processed.
+    fn save(&mut self, id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Checkpoints kept in memory, mostly useful for tests.
+impl CheckpointStore for HashSet<String> {
+    fn load(&self) -> Result<HashSet<String>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(self.clone())
+    }
+
+    fn save(&mut self, id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.insert(id.into());
+        Ok(())
+    }
+}
+
+/// Checkpoints kept in a file, one identifier per line.
+#[derive(Debug)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    /// Creates a store backed by the given file, which is created on the
+    /// first checkpoint if it does not exist.
+    pub fn new<T>(path: T) -> Self
+    where
+        T: Into<PathBuf>,
+    {
+        Self { path: path.into() }
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    fn load(&self) -> Result<HashSet<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let file = match File::open(&self.path) {
+            Ok(f) => f,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(HashSet::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut res = HashSet::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if !line.is_empty() {
+                res.insert(line);
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn save(&mut self, id: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", id)?;
+        Ok(())
+    }
+}
+
+/// Handle used to stop a running [`BulkExecutor`].
+///
+/// Once cancelled, no new item is started; the items in flight are
+/// allowed to finish so that the checkpoints stay accurate.
+#[derive(Debug, Clone, Default)]
+pub struct BulkCancel(Arc<AtomicBool>);
+
+impl BulkCancel {
+    /// Requests the executor to stop.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether a stop was requested.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Completion statistics of a run of [`BulkExecutor::run`].
+#[derive(Debug, Default)]
+pub struct BulkStats {
+    /// Number of items in the job
+    pub total: usize,
+    /// Items skipped because a previous run already processed them
+    pub skipped: usize,
+    /// Items processed successfully during this run
+    pub succeeded: usize,
+    /// Items whose operation failed, with the error message
+    pub failed: Vec<(String, String)>,
+    /// Items not attempted because the run was cancelled or timed out
+    pub remaining: usize,
+    /// Duration of the run
+    pub elapsed: Duration,
+}
+
+impl BulkStats {
+    /// Whether every item of the job has been processed successfully.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty() && self.remaining == 0
+    }
+}
+
+/// Executor of resumable bulk operations.
+#[derive(Debug, Clone)]
+pub struct BulkExecutor {
+    concurrency: usize,
+    time_limit: Option<Duration>,
+    cancel: BulkCancel,
+}
+
+impl Default for BulkExecutor {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            time_limit: None,
+            cancel: BulkCancel::default(),
+        }
+    }
+}
+
+impl BulkExecutor {
+    /// Creates an executor running 8 items concurrently, without time limit.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of items processed concurrently.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Stops starting new items once `limit` has elapsed since the start
+    /// of the run.
+    pub fn time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Returns a handle that can be used to stop the executor from another
+    /// task.
+    pub fn cancel_handle(&self) -> BulkCancel {
+        self.cancel.clone()
+    }
+
+    /// Runs `op` for every item of `ids` not already recorded in `store`.
+    ///
+    /// Successful items are recorded in `store` as soon as they complete,
+    /// failed ones are reported in the statistics and attempted again on
+    /// the next run.
+    ///
+    /// ```
+    /// use std::collections::HashSet;
+    /// use ovh::bulk::BulkExecutor;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut store = HashSet::new();
+    ///     store.insert("1".to_string());
+    ///
+    ///     let ids = vec!["1".to_string(), "2".to_string(), "3".to_string()];
+    ///     let stats = BulkExecutor::new()
+    ///         .run(&mut store, ids, |id| async move {
+    ///             if id == "3" {
+    ///                 return Err("boom".into());
+    ///             }
+    ///             Ok(())
+    ///         })
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     assert_eq!(stats.skipped, 1);
+    ///     assert_eq!(stats.succeeded, 1);
+    ///     assert_eq!(stats.failed, vec![("3".to_string(), "boom".to_string())]);
+    ///     assert!(store.contains("2"));
+    /// }
+    /// ```
+    pub async fn run<S, I, F, Fut>(
+        &self,
+        store: &mut S,
+        ids: I,
+        op: F,
+    ) -> Result<BulkStats, Box<dyn std::error::Error + Send + Sync>>
+    where
+        S: CheckpointStore + ?Sized,
+        I: IntoIterator<Item = String>,
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Result<(), Box<dyn std::error::Error + Send + Sync>>>,
+    {
+        let start = Instant::now();
+        let done = store.load()?;
+
+        let ids: Vec<_> = ids.into_iter().collect();
+        let total = ids.len();
+        let pending: Vec<_> = ids.into_iter().filter(|id| !done.contains(id)).collect();
+        let mut stats = BulkStats {
+            total,
+            skipped: total - pending.len(),
+            ..Default::default()
+        };
+
+        let mut results = futures::stream::iter(pending)
+            .take_while(|_| futures::future::ready(!self.should_stop(start)))
+            .map(|id| {
+                let fut = op(id.clone());
+                async move { (id, fut.await) }
+            })
+            .buffer_unordered(self.concurrency);
+
+        while let Some((id, res)) = results.next().await {
+            match res {
+                Ok(()) => {
+                    store.save(&id)?;
+                    stats.succeeded += 1;
+                }
+                Err(e) => stats.failed.push((id, e.to_string())),
+            }
+        }
+
+        stats.remaining = total - stats.skipped - stats.succeeded - stats.failed.len();
+        stats.elapsed = start.elapsed();
+
+        Ok(stats)
+    }
+
+    fn should_stop(&self, start: Instant) -> bool {
+        self.cancel.is_cancelled() || self.time_limit.is_some_and(|l| start.elapsed() >= l)
+    }
+}
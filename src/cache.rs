@@ -0,0 +1,114 @@
+//! Client-side caching of GET responses.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use reqwest::{
+    header::{HeaderMap, HeaderValue, ETAG},
+    Response, StatusCode,
+};
+
+/// Cache of the GET responses carrying an `ETag`.
+///
+/// Once a response is cached, the next GET requests to the same URL are
+/// sent with an `If-None-Match` header. When the API answers that the
+/// resource did not change (`304 Not Modified`), the cached response is
+/// returned instead, saving the transfer of the body. The API decides
+/// whether a cached response is still valid, so it is never stale.
+///
+/// ```
+/// use ovh::cache::EtagCache;
+/// use ovh::client::OvhClient;
+///
+/// let client = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key")
+///     .unwrap()
+///     .with_etag_cache(EtagCache::new());
+/// assert!(client.etag_cache().unwrap().is_empty());
+/// ```
+#[derive(Debug, Default)]
+pub struct EtagCache {
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+#[derive(Debug)]
+struct CachedResponse {
+    etag: HeaderValue,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl EtagCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of cached responses.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Returns whether no response is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().unwrap().is_empty()
+    }
+
+    /// Removes every cached response.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Returns the `ETag` of the response cached for a URL, if any.
+    pub(crate) fn etag(&self, url: &str) -> Option<HeaderValue> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(url).map(|e| e.etag.clone())
+    }
+
+    /// Resolves the response to a GET request sent with the `ETag` given
+    /// by [`etag`](Self::etag).
+    ///
+    /// A `304 Not Modified` response is replaced by the cached one, and a
+    /// successful response carrying an `ETag` is cached.
+    pub(crate) async fn resolve(
+        &self,
+        url: &str,
+        resp: Response,
+    ) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
+        if resp.status() == StatusCode::NOT_MODIFIED {
+            let entries = self.entries.lock().unwrap();
+            if let Some(e) = entries.get(url) {
+                return rebuild(StatusCode::OK, e.headers.clone(), e.body.clone());
+            }
+            return Ok(resp);
+        }
+
+        let etag = match resp.headers().get(ETAG) {
+            Some(etag) if resp.status() == StatusCode::OK => etag.clone(),
+            _ => return Ok(resp),
+        };
+
+        let headers = resp.headers().clone();
+        let body = resp.bytes().await?.to_vec();
+        self.entries.lock().unwrap().insert(
+            url.into(),
+            CachedResponse {
+                etag,
+                headers: headers.clone(),
+                body: body.clone(),
+            },
+        );
+        rebuild(StatusCode::OK, headers, body)
+    }
+}
+
+/// Builds a response from its parts, once its body has been read.
+pub(crate) fn rebuild(
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
+    let mut builder = http::Response::builder().status(status);
+    if let Some(h) = builder.headers_mut() {
+        *h = headers;
+    }
+    Ok(Response::from(builder.body(body)?))
+}
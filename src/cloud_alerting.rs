@@ -0,0 +1,279 @@
+//! High-level access to the budget alerts of Public Cloud projects.
+
+use crate::client::OvhClient;
+use crate::fetch::fetch_all;
+
+use serde::{Deserialize, Serialize};
+
+/// Structure representing a budget alert of a project, sending an email
+/// when the consumption of the month exceeds a threshold.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudAlerting {
+    /// Unique identifier of the alert
+    pub id: String,
+    /// Email address notified
+    pub email: String,
+    /// Threshold of the consumption of the month, in the currency of the
+    /// account
+    #[serde(rename(deserialize = "monthlyThreshold"))]
+    pub monthly_threshold: u64,
+    /// Minimum delay between two notifications, in seconds
+    pub delay: u64,
+    /// Creation date of the alert
+    #[serde(rename(deserialize = "creationDate"))]
+    pub creation_date: String,
+}
+
+/// Parameters of a budget alert to create or update.
+#[derive(Debug, Clone, Serialize)]
+pub struct OvhCloudAlertingCreate {
+    /// Email address to notify
+    pub email: String,
+    /// Threshold of the consumption of the month, in the currency of the
+    /// account
+    #[serde(rename(serialize = "monthlyThreshold"))]
+    pub monthly_threshold: u64,
+    /// Minimum delay between two notifications, in seconds: 3600, 21600,
+    /// 43200 or 86400
+    pub delay: u64,
+}
+
+impl OvhCloudAlertingCreate {
+    /// Creates the parameters of an alert notifying `email` at most once an
+    /// hour.
+    pub fn new(email: &str, monthly_threshold: u64) -> Self {
+        Self {
+            email: email.into(),
+            monthly_threshold,
+            delay: 3600,
+        }
+    }
+}
+
+/// Structure representing a notification sent by a budget alert.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudAlertingAlert {
+    /// Unique identifier of the notification
+    #[serde(rename(deserialize = "alertId"))]
+    pub alert_id: u64,
+    /// Date the notification was sent
+    #[serde(rename(deserialize = "alertDate"))]
+    pub alert_date: String,
+    /// Email addresses notified
+    pub emails: Vec<String>,
+}
+
+/// Consumption of a project compared to the threshold of a budget alert.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OvhCloudBudgetStatus {
+    /// Threshold of the alert
+    pub threshold: u64,
+    /// Consumption of the current month
+    pub current: f64,
+}
+
+impl OvhCloudBudgetStatus {
+    /// Returns the share of the threshold consumed, 1.0 meaning that it is
+    /// reached.
+    ///
+    /// ```
+    /// use ovh::cloud_alerting::OvhCloudBudgetStatus;
+    ///
+    /// let status = OvhCloudBudgetStatus { threshold: 200, current: 150.0 };
+    /// assert_eq!(status.ratio(), 0.75);
+    /// assert!(!status.is_exceeded());
+    /// ```
+    pub fn ratio(&self) -> f64 {
+        if self.threshold == 0 {
+            return f64::INFINITY;
+        }
+        self.current / self.threshold as f64
+    }
+
+    /// Whether the consumption exceeds the threshold.
+    pub fn is_exceeded(&self) -> bool {
+        self.current > self.threshold as f64
+    }
+}
+
+/// Consumption of a project since the start of the month, as returned by
+/// `/usage/current`.
+#[derive(Debug, Deserialize)]
+struct UsageCurrent {
+    #[serde(rename(deserialize = "hourlyUsage"))]
+    hourly_usage: Option<serde_json::Value>,
+    #[serde(rename(deserialize = "monthlyUsage"))]
+    monthly_usage: Option<serde_json::Value>,
+    #[serde(rename(deserialize = "resourcesUsage"), default)]
+    resources_usage: Vec<serde_json::Value>,
+}
+
+impl UsageCurrent {
+    /// Sums the prices of every product, without their details, which
+    /// repeat them.
+    fn total(&self) -> f64 {
+        fn price(v: &serde_json::Value) -> f64 {
+            v.get("totalPrice")
+                .and_then(|p| p.as_f64())
+                .unwrap_or_default()
+        }
+        fn products(usage: &Option<serde_json::Value>) -> f64 {
+            let map = match usage.as_ref().and_then(|u| u.as_object()) {
+                Some(map) => map,
+                None => return 0.0,
+            };
+            map.values()
+                .filter_map(|v| v.as_array())
+                .flatten()
+                .map(price)
+                .sum()
+        }
+
+        products(&self.hourly_usage)
+            + products(&self.monthly_usage)
+            + self.resources_usage.iter().map(price).sum::<f64>()
+    }
+}
+
+impl OvhCloudAlerting {
+    /// Lists the budget alerts of a project.
+    pub async fn list(
+        c: &OvhClient,
+        project: &str,
+    ) -> Result<Vec<OvhCloudAlerting>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids: Vec<String> = c
+            .get_json(&format!("/cloud/project/{}/alerting", project))
+            .await?;
+        let res = fetch_all(&ids, c.fetch_concurrency(), |id| Self::get(c, project, id)).await;
+        res.into_iter().collect()
+    }
+
+    /// Retrieves a budget alert.
+    pub async fn get(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+    ) -> Result<OvhCloudAlerting, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/cloud/project/{}/alerting/{}", project, id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Creates a budget alert.
+    ///
+    /// Each alert notifies a single address: several alerts with the same
+    /// threshold notify several people.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::cloud_alerting::{OvhCloudAlerting, OvhCloudAlertingCreate};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     for email in ["finance@example.com", "ops@example.com"] {
+    ///         let params = OvhCloudAlertingCreate::new(email, 500);
+    ///         OvhCloudAlerting::create(&c, "project_id", &params)
+    ///             .await
+    ///             .unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub async fn create(
+        c: &OvhClient,
+        project: &str,
+        params: &OvhCloudAlertingCreate,
+    ) -> Result<OvhCloudAlerting, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .post_json(&format!("/cloud/project/{}/alerting", project), params)
+            .await?;
+        Ok(res)
+    }
+
+    /// Changes the address, threshold and delay of a budget alert.
+    pub async fn update(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        params: &OvhCloudAlertingCreate,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let resp = c
+            .put(
+                &format!("/cloud/project/{}/alerting/{}", project, id),
+                params,
+            )
+            .await?;
+        OvhClient::ensure_success(resp).await?;
+        Ok(())
+    }
+
+    /// Deletes a budget alert.
+    pub async fn delete(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let resp = c
+            .delete(&format!("/cloud/project/{}/alerting/{}", project, id))
+            .await?;
+        OvhClient::ensure_success(resp).await?;
+        Ok(())
+    }
+
+    /// Lists the notifications sent by a budget alert.
+    pub async fn alerts(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+    ) -> Result<Vec<OvhCloudAlertingAlert>, Box<dyn std::error::Error + Send + Sync>> {
+        let path = format!("/cloud/project/{}/alerting/{}/alert", project, id);
+        let ids: Vec<u64> = c.get_json(&path).await?;
+        let paths: Vec<String> = ids.iter().map(|a| format!("{}/{}", path, a)).collect();
+        let res = fetch_all(&paths, c.fetch_concurrency(), |p| c.get_json(p)).await;
+        res.into_iter().collect()
+    }
+
+    /// Retrieves the consumption of a project since the start of the month,
+    /// in the currency of the account.
+    pub async fn current_consumption(
+        c: &OvhClient,
+        project: &str,
+    ) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
+        let usage: UsageCurrent = c
+            .get_json(&format!("/cloud/project/{}/usage/current", project))
+            .await?;
+        Ok(usage.total())
+    }
+
+    /// Compares the consumption of a project with the threshold of a budget
+    /// alert.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::cloud_alerting::OvhCloudAlerting;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let status = OvhCloudAlerting::status(&c, "project_id", "alert_id")
+    ///         .await
+    ///         .unwrap();
+    ///     println!("{:.0}% of the budget consumed", status.ratio() * 100.0);
+    /// }
+    /// ```
+    pub async fn status(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+    ) -> Result<OvhCloudBudgetStatus, Box<dyn std::error::Error + Send + Sync>> {
+        let (alert, current) = futures::try_join!(
+            Self::get(c, project, id),
+            Self::current_consumption(c, project)
+        )?;
+        Ok(OvhCloudBudgetStatus {
+            threshold: alert.monthly_threshold,
+            current,
+        })
+    }
+}
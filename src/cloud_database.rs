@@ -0,0 +1,800 @@
+//! High-level access to the Public Cloud managed database API.
+
+use crate::client::OvhClient;
+use crate::fetch::fetch_all;
+
+use serde::{Deserialize, Serialize};
+
+/// Structure representing a managed database service.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudDatabase {
+    /// Unique identifier of the service
+    pub id: String,
+    /// Description of the service
+    pub description: String,
+    /// Engine of the service, such as `postgresql`
+    pub engine: String,
+    /// Version of the engine
+    pub version: String,
+    /// Current status of the service (`CREATING`, `READY`...)
+    pub status: String,
+    /// Endpoints the service can be reached at
+    pub endpoints: Vec<OvhCloudDatabaseEndpoint>,
+}
+
+/// Structure representing an endpoint of a managed database service.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudDatabaseEndpoint {
+    /// Component served by the endpoint, such as `postgresql` or
+    /// `postgresqlRead`
+    pub component: String,
+    /// Host name of the endpoint
+    pub domain: String,
+    /// Port of the endpoint
+    pub port: Option<u16>,
+    /// Connection URI template, with placeholders for the credentials
+    pub uri: Option<String>,
+    /// Whether the endpoint requires TLS
+    pub ssl: bool,
+    /// TLS mode expected by the engine client, such as `require`
+    #[serde(rename(deserialize = "sslMode"))]
+    pub ssl_mode: Option<String>,
+}
+
+/// Everything needed to connect to a managed database service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OvhConnectionInfo {
+    /// Connection URI template, with placeholders for the credentials
+    pub uri: Option<String>,
+    /// Host name of the service
+    pub host: String,
+    /// Port of the service
+    pub port: Option<u16>,
+    /// TLS mode expected by the engine client, if TLS is required
+    pub ssl_mode: Option<String>,
+    /// PEM-encoded CA certificate to validate the service certificate
+    pub ca_cert: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OvhCloudDatabaseCertificates {
+    ca: String,
+}
+
+impl OvhCloudDatabase {
+    /// Retrieves a managed database service.
+    pub async fn get(
+        c: &OvhClient,
+        project: &str,
+        engine: &str,
+        id: &str,
+    ) -> Result<OvhCloudDatabase, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/{}/{}",
+                project, engine, id
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Retrieves the CA certificate of a managed database service, if the
+    /// engine uses one.
+    pub async fn ca_certificate(
+        c: &OvhClient,
+        project: &str,
+        engine: &str,
+        id: &str,
+    ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_opt::<OvhCloudDatabaseCertificates>(&format!(
+                "/cloud/project/{}/database/{}/{}/certificates",
+                project, engine, id
+            ))
+            .await?;
+        Ok(res.map(|r| r.ca))
+    }
+
+    /// Retrieves the connection information of the main endpoint of a
+    /// managed database service, including its CA certificate.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::cloud_database::OvhCloudDatabase;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let info = OvhCloudDatabase::connection_info(&c, "project_id", "postgresql", "service_id")
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     std::fs::write("ca.pem", info.ca_cert.unwrap()).unwrap();
+    ///     println!("{}", info.uri.unwrap());
+    /// }
+    /// ```
+    pub async fn connection_info(
+        c: &OvhClient,
+        project: &str,
+        engine: &str,
+        id: &str,
+    ) -> Result<OvhConnectionInfo, Box<dyn std::error::Error + Send + Sync>> {
+        let service = Self::get(c, project, engine, id).await?;
+
+        let mut endpoints = service.endpoints;
+        let pos = endpoints
+            .iter()
+            .position(|e| e.component == engine)
+            .unwrap_or(0);
+        if pos >= endpoints.len() {
+            return Err("service has no endpoint".into());
+        }
+        let endpoint = endpoints.swap_remove(pos);
+
+        let ca_cert = if endpoint.ssl {
+            Self::ca_certificate(c, project, engine, id).await?
+        } else {
+            None
+        };
+
+        Ok(OvhConnectionInfo {
+            uri: endpoint.uri,
+            host: endpoint.domain,
+            port: endpoint.port,
+            ssl_mode: endpoint.ssl_mode,
+            ca_cert,
+        })
+    }
+}
+
+/// Structure representing a user of a managed database service.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudDatabaseUser {
+    /// Unique identifier of the user
+    pub id: String,
+    /// Name of the user
+    pub username: String,
+    /// Current status of the user (`CREATING`, `READY`...)
+    pub status: String,
+    /// Password of the user, only returned on creation
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhCloudDatabaseUserCreate<'a> {
+    name: &'a str,
+}
+
+impl OvhCloudDatabaseUser {
+    /// Lists all of the users of a service.
+    ///
+    /// This method will perform one extra API call per user in order to
+    /// get their details.
+    pub async fn list(
+        c: &OvhClient,
+        project: &str,
+        engine: &str,
+        id: &str,
+    ) -> Result<Vec<OvhCloudDatabaseUser>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids: Vec<String> = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/{}/{}/user",
+                project, engine, id
+            ))
+            .await?;
+        let res = fetch_all(&ids, c.fetch_concurrency(), |user_id| {
+            Self::get(c, project, engine, id, user_id)
+        })
+        .await;
+        res.into_iter().collect()
+    }
+
+    /// Retrieves a user of a service.
+    pub async fn get(
+        c: &OvhClient,
+        project: &str,
+        engine: &str,
+        id: &str,
+        user_id: &str,
+    ) -> Result<OvhCloudDatabaseUser, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/{}/{}/user/{}",
+                project, engine, id, user_id
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Creates a new user on a service.
+    pub async fn create(
+        c: &OvhClient,
+        project: &str,
+        engine: &str,
+        id: &str,
+        name: &str,
+    ) -> Result<OvhCloudDatabaseUser, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhCloudDatabaseUserCreate { name };
+        let res = c
+            .post_json(
+                &format!("/cloud/project/{}/database/{}/{}/user", project, engine, id),
+                &data,
+            )
+            .await?;
+        Ok(res)
+    }
+
+    /// Deletes a user of a service.
+    pub async fn delete(
+        c: &OvhClient,
+        project: &str,
+        engine: &str,
+        id: &str,
+        user_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.delete(&format!(
+            "/cloud/project/{}/database/{}/{}/user/{}",
+            project, engine, id, user_id
+        ))
+        .await?
+        .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Structure representing a Kafka topic.
+#[derive(Debug, Deserialize)]
+pub struct OvhKafkaTopic {
+    /// Unique identifier of the topic
+    pub id: String,
+    /// Name of the topic
+    pub name: String,
+    /// Number of partitions
+    pub partitions: u32,
+    /// Number of replicas of every partition
+    pub replication: u32,
+    /// Minimum number of in-sync replicas for a write to succeed
+    #[serde(rename(deserialize = "minInsyncReplicas"))]
+    pub min_insync_replicas: u32,
+    /// Maximum size of a partition before old messages are discarded, in
+    /// bytes, or -1 for no limit
+    #[serde(rename(deserialize = "retentionBytes"))]
+    pub retention_bytes: i64,
+    /// Duration messages are kept for, in hours, or -1 for no limit
+    #[serde(rename(deserialize = "retentionHours"))]
+    pub retention_hours: i64,
+}
+
+/// Parameters of a Kafka topic to create.
+///
+/// Settings left to `None` use the defaults of the service.
+#[derive(Debug, Serialize)]
+pub struct OvhKafkaTopicCreate {
+    /// Name of the topic
+    pub name: String,
+    /// Number of partitions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partitions: Option<u32>,
+    /// Number of replicas of every partition
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replication: Option<u32>,
+    /// Minimum number of in-sync replicas for a write to succeed
+    #[serde(
+        rename(serialize = "minInsyncReplicas"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub min_insync_replicas: Option<u32>,
+    /// Maximum size of a partition before old messages are discarded, in
+    /// bytes, or -1 for no limit
+    #[serde(
+        rename(serialize = "retentionBytes"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retention_bytes: Option<i64>,
+    /// Duration messages are kept for, in hours, or -1 for no limit
+    #[serde(
+        rename(serialize = "retentionHours"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retention_hours: Option<i64>,
+}
+
+impl OvhKafkaTopicCreate {
+    /// Creates the parameters of a topic using the defaults of the service.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            partitions: None,
+            replication: None,
+            min_insync_replicas: None,
+            retention_bytes: None,
+            retention_hours: None,
+        }
+    }
+}
+
+impl OvhKafkaTopic {
+    /// Lists all of the topics of a Kafka service.
+    ///
+    /// This method will perform one extra API call per topic in order to
+    /// get their details.
+    pub async fn list(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+    ) -> Result<Vec<OvhKafkaTopic>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids: Vec<String> = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/kafka/{}/topic",
+                project, id
+            ))
+            .await?;
+        let res = fetch_all(&ids, c.fetch_concurrency(), |topic_id| {
+            Self::get(c, project, id, topic_id)
+        })
+        .await;
+        res.into_iter().collect()
+    }
+
+    /// Retrieves a topic of a Kafka service.
+    pub async fn get(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        topic_id: &str,
+    ) -> Result<OvhKafkaTopic, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/kafka/{}/topic/{}",
+                project, id, topic_id
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Creates a new topic on a Kafka service.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::cloud_database::{OvhKafkaAcl, OvhKafkaTopic, OvhKafkaTopicCreate};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let mut params = OvhKafkaTopicCreate::new("events");
+    ///     params.partitions = Some(6);
+    ///     params.retention_hours = Some(72);
+    ///
+    ///     OvhKafkaTopic::create(&c, "project_id", "service_id", &params)
+    ///         .await
+    ///         .unwrap();
+    ///     OvhKafkaAcl::create(&c, "project_id", "service_id", "events", "consumer", "read")
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn create(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        params: &OvhKafkaTopicCreate,
+    ) -> Result<OvhKafkaTopic, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .post_json(
+                &format!("/cloud/project/{}/database/kafka/{}/topic", project, id),
+                params,
+            )
+            .await?;
+        Ok(res)
+    }
+
+    /// Deletes a topic of a Kafka service.
+    pub async fn delete(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        topic_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.delete(&format!(
+            "/cloud/project/{}/database/kafka/{}/topic/{}",
+            project, id, topic_id
+        ))
+        .await?
+        .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Structure representing a permission of a user on Kafka topics.
+#[derive(Debug, Deserialize)]
+pub struct OvhKafkaAcl {
+    /// Unique identifier of the ACL
+    pub id: String,
+    /// Granted permission (`admin`, `read`, `write` or `readwrite`)
+    pub permission: String,
+    /// Topic name or pattern, such as `events.*`
+    pub topic: String,
+    /// Name or pattern of the users the permission is granted to
+    pub username: String,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhKafkaAclCreate<'a> {
+    permission: &'a str,
+    topic: &'a str,
+    username: &'a str,
+}
+
+impl OvhKafkaAcl {
+    /// Lists all of the ACLs of a Kafka service.
+    ///
+    /// This method will perform one extra API call per ACL in order to
+    /// get their details.
+    pub async fn list(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+    ) -> Result<Vec<OvhKafkaAcl>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids: Vec<String> = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/kafka/{}/acl",
+                project, id
+            ))
+            .await?;
+        let res = fetch_all(&ids, c.fetch_concurrency(), |acl_id| {
+            Self::get(c, project, id, acl_id)
+        })
+        .await;
+        res.into_iter().collect()
+    }
+
+    /// Retrieves an ACL of a Kafka service.
+    pub async fn get(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        acl_id: &str,
+    ) -> Result<OvhKafkaAcl, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/kafka/{}/acl/{}",
+                project, id, acl_id
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Grants a permission on topics to users of a Kafka service.
+    pub async fn create(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        topic: &str,
+        username: &str,
+        permission: &str,
+    ) -> Result<OvhKafkaAcl, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhKafkaAclCreate {
+            permission,
+            topic,
+            username,
+        };
+        let res = c
+            .post_json(
+                &format!("/cloud/project/{}/database/kafka/{}/acl", project, id),
+                &data,
+            )
+            .await?;
+        Ok(res)
+    }
+
+    /// Revokes an ACL of a Kafka service.
+    pub async fn delete(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        acl_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.delete(&format!(
+            "/cloud/project/{}/database/kafka/{}/acl/{}",
+            project, id, acl_id
+        ))
+        .await?
+        .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Structure representing the client certificate of a Kafka user.
+#[derive(Debug, Deserialize)]
+pub struct OvhKafkaUserAccess {
+    /// PEM-encoded client certificate
+    pub cert: String,
+    /// PEM-encoded private key of the certificate
+    pub key: String,
+}
+
+impl OvhKafkaUserAccess {
+    /// Retrieves the client certificate a Kafka user authenticates with.
+    ///
+    /// The CA certificate of the service is retrieved with
+    /// [`OvhCloudDatabase::ca_certificate`].
+    pub async fn get(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        user_id: &str,
+    ) -> Result<OvhKafkaUserAccess, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/kafka/{}/user/{}/access",
+                project, id, user_id
+            ))
+            .await?;
+        Ok(res)
+    }
+}
+
+/// Structure representing an index pattern of an OpenSearch service.
+///
+/// Patterns limit the number of indices matching them, the oldest ones
+/// being deleted first.
+#[derive(Debug, Deserialize)]
+pub struct OvhOpenSearchPattern {
+    /// Unique identifier of the pattern
+    pub id: String,
+    /// Pattern of index names, such as `logs-*`
+    pub pattern: String,
+    /// Maximum number of indices matching the pattern, or 0 for no limit
+    #[serde(rename(deserialize = "maxIndexCount"))]
+    pub max_index_count: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhOpenSearchPatternCreate<'a> {
+    pattern: &'a str,
+    #[serde(rename(serialize = "maxIndexCount"))]
+    max_index_count: u64,
+}
+
+impl OvhOpenSearchPattern {
+    /// Lists all of the index patterns of an OpenSearch service.
+    ///
+    /// This method will perform one extra API call per pattern in order to
+    /// get their details.
+    pub async fn list(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+    ) -> Result<Vec<OvhOpenSearchPattern>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids: Vec<String> = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/opensearch/{}/pattern",
+                project, id
+            ))
+            .await?;
+        let res = fetch_all(&ids, c.fetch_concurrency(), |pattern_id| {
+            Self::get(c, project, id, pattern_id)
+        })
+        .await;
+        res.into_iter().collect()
+    }
+
+    /// Retrieves an index pattern of an OpenSearch service.
+    pub async fn get(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        pattern_id: &str,
+    ) -> Result<OvhOpenSearchPattern, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/opensearch/{}/pattern/{}",
+                project, id, pattern_id
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Creates a new index pattern on an OpenSearch service.
+    pub async fn create(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        pattern: &str,
+        max_index_count: u64,
+    ) -> Result<OvhOpenSearchPattern, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhOpenSearchPatternCreate {
+            pattern,
+            max_index_count,
+        };
+        let res = c
+            .post_json(
+                &format!(
+                    "/cloud/project/{}/database/opensearch/{}/pattern",
+                    project, id
+                ),
+                &data,
+            )
+            .await?;
+        Ok(res)
+    }
+
+    /// Deletes an index pattern of an OpenSearch service.
+    pub async fn delete(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        pattern_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.delete(&format!(
+            "/cloud/project/{}/database/opensearch/{}/pattern/{}",
+            project, id, pattern_id
+        ))
+        .await?
+        .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Permission of an OpenSearch user on the indices matching a pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OvhOpenSearchAcl {
+    /// Pattern of index names, such as `logs-*`
+    pub pattern: String,
+    /// Granted permission (`admin`, `read`, `write`, `readwrite` or
+    /// `deny`)
+    pub permission: String,
+}
+
+impl OvhOpenSearchAcl {
+    /// Creates a new permission.
+    pub fn new(pattern: &str, permission: &str) -> Self {
+        Self {
+            pattern: pattern.into(),
+            permission: permission.into(),
+        }
+    }
+}
+
+/// Structure representing a user of an OpenSearch service.
+#[derive(Debug, Deserialize)]
+pub struct OvhOpenSearchUser {
+    /// Unique identifier of the user
+    pub id: String,
+    /// Name of the user
+    pub username: String,
+    /// Current status of the user (`CREATING`, `READY`...)
+    pub status: String,
+    /// Password of the user, only returned on creation
+    pub password: Option<String>,
+    /// Permissions of the user
+    pub acls: Vec<OvhOpenSearchAcl>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhOpenSearchUserCreate<'a> {
+    name: &'a str,
+    acls: &'a [OvhOpenSearchAcl],
+}
+
+#[derive(Debug, Serialize)]
+struct OvhOpenSearchUserUpdate<'a> {
+    acls: &'a [OvhOpenSearchAcl],
+}
+
+impl OvhOpenSearchUser {
+    /// Lists all of the users of an OpenSearch service, along with their
+    /// permissions.
+    ///
+    /// This method will perform one extra API call per user in order to
+    /// get their details.
+    pub async fn list(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+    ) -> Result<Vec<OvhOpenSearchUser>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids: Vec<String> = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/opensearch/{}/user",
+                project, id
+            ))
+            .await?;
+        let res = fetch_all(&ids, c.fetch_concurrency(), |user_id| {
+            Self::get(c, project, id, user_id)
+        })
+        .await;
+        res.into_iter().collect()
+    }
+
+    /// Retrieves a user of an OpenSearch service, along with its permissions.
+    pub async fn get(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        user_id: &str,
+    ) -> Result<OvhOpenSearchUser, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/cloud/project/{}/database/opensearch/{}/user/{}",
+                project, id, user_id
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Creates a new user on an OpenSearch service.
+    ///
+    /// Permissions are only enforced once ACLs are enabled on the service.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::cloud_database::{OvhOpenSearchAcl, OvhOpenSearchPattern, OvhOpenSearchUser};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     OvhOpenSearchPattern::create(&c, "project_id", "service_id", "logs-*", 30)
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     let acls = [OvhOpenSearchAcl::new("logs-*", "write")];
+    ///     let user = OvhOpenSearchUser::create(&c, "project_id", "service_id", "shipper", &acls)
+    ///         .await
+    ///         .unwrap();
+    ///     println!("{}", user.password.unwrap());
+    /// }
+    /// ```
+    pub async fn create(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        name: &str,
+        acls: &[OvhOpenSearchAcl],
+    ) -> Result<OvhOpenSearchUser, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhOpenSearchUserCreate { name, acls };
+        let res = c
+            .post_json(
+                &format!("/cloud/project/{}/database/opensearch/{}/user", project, id),
+                &data,
+            )
+            .await?;
+        Ok(res)
+    }
+    /// Replaces the permissions of a user of an OpenSearch service.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::cloud_database::{OvhOpenSearchAcl, OvhOpenSearchUser};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let acls = vec![
+    ///         OvhOpenSearchAcl::new("logs-*", "read"),
+    ///         OvhOpenSearchAcl::new("metrics-*", "readwrite"),
+    ///     ];
+    ///     OvhOpenSearchUser::set_acls(&c, "project_id", "service_id", "user_id", acls)
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn set_acls(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+        user_id: &str,
+        acls: Vec<OvhOpenSearchAcl>,
+    ) -> Result<OvhOpenSearchUser, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhOpenSearchUserUpdate { acls: &acls };
+        let res = c
+            .put_json(
+                &format!(
+                    "/cloud/project/{}/database/opensearch/{}/user/{}",
+                    project, id, user_id
+                ),
+                &data,
+            )
+            .await?;
+        Ok(res)
+    }
+}
@@ -0,0 +1,138 @@
+//! High-level access to the Public Cloud instance API.
+
+use crate::client::OvhClient;
+
+use serde::{Deserialize, Serialize};
+
+/// Structure representing a Public Cloud instance.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudInstance {
+    /// Unique identifier of the instance
+    pub id: String,
+    /// Name of the instance
+    pub name: String,
+    /// Current status of the instance (`BUILD`, `ACTIVE`, `ERROR`...)
+    pub status: String,
+    /// Region the instance runs in
+    pub region: String,
+    /// Flavor of the instance
+    #[serde(rename(deserialize = "flavorId"))]
+    pub flavor_id: String,
+    /// Image the instance was created from
+    #[serde(rename(deserialize = "imageId"))]
+    pub image_id: Option<String>,
+}
+
+/// Parameters of an instance to create.
+#[derive(Debug, Serialize)]
+pub struct OvhCloudInstanceCreate {
+    /// Name of the instance
+    pub name: String,
+    /// Region to create the instance in
+    pub region: String,
+    /// Flavor of the instance
+    #[serde(rename(serialize = "flavorId"))]
+    pub flavor_id: String,
+    /// Image to boot the instance from
+    #[serde(rename(serialize = "imageId"))]
+    pub image_id: String,
+    /// SSH key to install on the instance
+    #[serde(
+        rename(serialize = "sshKeyId"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub ssh_key_id: Option<String>,
+    /// cloud-init user data, run by the instance on first boot
+    #[serde(
+        rename(serialize = "userData"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub user_data: Option<String>,
+    /// Bill the instance monthly instead of hourly
+    #[serde(rename(serialize = "monthlyBilling"))]
+    pub monthly_billing: bool,
+}
+
+impl OvhCloudInstanceCreate {
+    /// Creates the parameters of an hourly billed instance without SSH key
+    /// nor user data.
+    pub fn new(name: &str, region: &str, flavor_id: &str, image_id: &str) -> Self {
+        Self {
+            name: name.into(),
+            region: region.into(),
+            flavor_id: flavor_id.into(),
+            image_id: image_id.into(),
+            ssh_key_id: None,
+            user_data: None,
+            monthly_billing: false,
+        }
+    }
+}
+
+/// Structure representing a remote console access.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudInstanceVnc {
+    /// Kind of console (usually `novnc`)
+    #[serde(rename(deserialize = "type"))]
+    pub kind: String,
+    /// URL of the console, valid for a limited time
+    pub url: String,
+}
+
+impl OvhCloudInstance {
+    /// Retrieves an instance.
+    pub async fn get(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+    ) -> Result<OvhCloudInstance, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/cloud/project/{}/instance/{}", project, id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Creates a new instance.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::cloud_instance::{OvhCloudInstance, OvhCloudInstanceCreate};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let mut params = OvhCloudInstanceCreate::new("web1", "GRA11", "flavor_id", "image_id");
+    ///     params.user_data = Some("#cloud-config\npackages:\n  - nginx\n".into());
+    ///
+    ///     let instance = OvhCloudInstance::create(&c, "project_id", &params)
+    ///         .await
+    ///         .unwrap();
+    ///     println!("{}", instance.id);
+    /// }
+    /// ```
+    pub async fn create(
+        c: &OvhClient,
+        project: &str,
+        params: &OvhCloudInstanceCreate,
+    ) -> Result<OvhCloudInstance, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .post_json(&format!("/cloud/project/{}/instance", project), params)
+            .await?;
+        Ok(res)
+    }
+
+    /// Requests a remote console URL for an instance.
+    pub async fn vnc(
+        c: &OvhClient,
+        project: &str,
+        id: &str,
+    ) -> Result<OvhCloudInstanceVnc, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .post_json(
+                &format!("/cloud/project/{}/instance/{}/vnc", project, id),
+                &serde_json::json!({}),
+            )
+            .await?;
+        Ok(res)
+    }
+}
@@ -0,0 +1,277 @@
+//! High-level access to the Public Cloud project API.
+
+use crate::client::OvhClient;
+
+use serde::Deserialize;
+
+/// Structure representing a Public Cloud project.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudProject {
+    /// Unique identifier of the project
+    #[serde(rename(deserialize = "project_id"))]
+    pub id: String,
+    /// Description of the project
+    pub description: Option<String>,
+    /// Current status of the project (`ok`, `suspended`...)
+    pub status: String,
+}
+
+/// Structure representing the availability of the Public Cloud products.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudAvailability {
+    /// Availability of every plan
+    pub plans: Vec<OvhCloudPlanAvailability>,
+}
+
+/// Structure representing the regions a plan is available in.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudPlanAvailability {
+    /// Code of the plan, such as `b2-7.consumption`
+    pub code: String,
+    /// Regions the plan is available in
+    pub regions: Vec<OvhCloudRegionAvailability>,
+}
+
+/// Structure representing a region a plan is available in.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudRegionAvailability {
+    /// Name of the region, such as `GRA11`
+    pub name: String,
+    /// Datacenter of the region, such as `GRA`
+    pub datacenter: String,
+}
+
+impl OvhCloudAvailability {
+    /// Checks whether a plan is available in a region.
+    ///
+    /// ```
+    /// use ovh::cloud_project::OvhCloudAvailability;
+    ///
+    /// let availability: OvhCloudAvailability = serde_json::from_str(r#"{
+    ///     "plans": [{
+    ///         "code": "b2-7.consumption",
+    ///         "regions": [{"name": "GRA11", "datacenter": "GRA"}]
+    ///     }]
+    /// }"#).unwrap();
+    ///
+    /// assert!(availability.is_available("b2-7.consumption", "GRA11"));
+    /// assert!(!availability.is_available("b2-7.consumption", "BHS5"));
+    /// ```
+    pub fn is_available(&self, plan: &str, region: &str) -> bool {
+        self.plans
+            .iter()
+            .filter(|p| p.code == plan)
+            .flat_map(|p| &p.regions)
+            .any(|r| r.name == region)
+    }
+}
+
+/// Structure representing an instance flavor.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudFlavor {
+    /// Unique identifier of the flavor in its region
+    pub id: String,
+    /// Name of the flavor, such as `b2-7`
+    pub name: String,
+    /// Region of the flavor
+    pub region: String,
+    /// Number of virtual CPUs
+    pub vcpus: u32,
+    /// Memory, in GB
+    pub ram: u32,
+    /// Disk size, in GB
+    pub disk: u32,
+    /// Whether instances can currently be created with the flavor
+    pub available: bool,
+}
+
+/// Structure representing the engines, flavors and regions available for
+/// managed databases.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudDatabaseCapabilities {
+    /// Available engines
+    pub engines: Vec<OvhCloudDatabaseEngine>,
+    /// Available regions
+    pub regions: Vec<String>,
+}
+
+/// Structure representing a managed database engine.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudDatabaseEngine {
+    /// Name of the engine, such as `postgresql`
+    pub name: String,
+    /// Versions of the engine that can be deployed
+    pub versions: Vec<String>,
+    /// Version deployed when none is specified
+    #[serde(rename(deserialize = "defaultVersion"))]
+    pub default_version: String,
+}
+
+impl OvhCloudProject {
+    /// Retrieves a project.
+    pub async fn get(
+        c: &OvhClient,
+        project: &str,
+    ) -> Result<OvhCloudProject, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/cloud/project/{}", project)).await?;
+        Ok(res)
+    }
+
+    /// Retrieves the regions every plan is available in.
+    ///
+    /// `subsidiary` is the OVH subsidiary of the account, such as `FR`.
+    pub async fn availability(
+        c: &OvhClient,
+        project: &str,
+        subsidiary: &str,
+    ) -> Result<OvhCloudAvailability, Box<dyn std::error::Error + Send + Sync>> {
+        let resp = c
+            .get_with_params(
+                &format!(
+                    "/cloud/project/{}/capabilities/productAvailability",
+                    project
+                ),
+                &[("ovhSubsidiary", subsidiary)],
+            )
+            .await?;
+        let res = c.read_json(resp).await?;
+        Ok(res)
+    }
+
+    /// Lists the instance flavors of a region.
+    pub async fn flavors(
+        c: &OvhClient,
+        project: &str,
+        region: &str,
+    ) -> Result<Vec<OvhCloudFlavor>, Box<dyn std::error::Error + Send + Sync>> {
+        let resp = c
+            .get_with_params(
+                &format!("/cloud/project/{}/flavor", project),
+                &[("region", region)],
+            )
+            .await?;
+        let res = c.read_json(resp).await?;
+        Ok(res)
+    }
+
+    /// Retrieves a flavor of a region by name, failing with the list of
+    /// the available flavors if it does not exist or cannot be used.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::cloud_instance::{OvhCloudInstance, OvhCloudInstanceCreate};
+    /// use ovh::cloud_project::OvhCloudProject;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let flavor = OvhCloudProject::require_flavor(&c, "project_id", "GRA11", "b2-7")
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     let params = OvhCloudInstanceCreate::new("web1", "GRA11", &flavor.id, "image_id");
+    ///     OvhCloudInstance::create(&c, "project_id", &params)
+    ///         .await
+    ///         .unwrap();
+    /// }
+    /// ```
+    pub async fn require_flavor(
+        c: &OvhClient,
+        project: &str,
+        region: &str,
+        name: &str,
+    ) -> Result<OvhCloudFlavor, Box<dyn std::error::Error + Send + Sync>> {
+        let (found, others): (Vec<_>, Vec<_>) = Self::flavors(c, project, region)
+            .await?
+            .into_iter()
+            .filter(|f| f.available)
+            .partition(|f| f.name == name);
+
+        if let Some(f) = found.into_iter().next() {
+            return Ok(f);
+        }
+
+        let mut names: Vec<_> = others.into_iter().map(|f| f.name).collect();
+        names.sort();
+        names.dedup();
+        Err(format!(
+            "flavor `{}` is not available in region {}, available flavors: {}",
+            name,
+            region,
+            names.join(", ")
+        )
+        .into())
+    }
+
+    /// Lists the regions Managed Kubernetes clusters can be created in.
+    pub async fn kube_regions(
+        c: &OvhClient,
+        project: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/cloud/project/{}/capabilities/kube/regions",
+                project
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Retrieves the engines, versions and regions available for managed
+    /// databases.
+    pub async fn database_capabilities(
+        c: &OvhClient,
+        project: &str,
+    ) -> Result<OvhCloudDatabaseCapabilities, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/cloud/project/{}/database/capabilities", project))
+            .await?;
+        Ok(res)
+    }
+
+    /// Checks that a managed database engine version can be deployed in a
+    /// region, failing with the available alternatives otherwise.
+    pub async fn require_database(
+        c: &OvhClient,
+        project: &str,
+        region: &str,
+        engine: &str,
+        version: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let caps = Self::database_capabilities(c, project).await?;
+
+        if !caps.regions.iter().any(|r| r == region) {
+            return Err(format!(
+                "managed databases are not available in region {}, available regions: {}",
+                region,
+                caps.regions.join(", ")
+            )
+            .into());
+        }
+
+        let e = caps
+            .engines
+            .iter()
+            .find(|e| e.name == engine)
+            .ok_or_else(|| {
+                let names: Vec<_> = caps.engines.iter().map(|e| e.name.as_str()).collect();
+                format!(
+                    "unknown database engine `{}`, available engines: {}",
+                    engine,
+                    names.join(", ")
+                )
+            })?;
+
+        if !e.versions.iter().any(|v| v == version) {
+            return Err(format!(
+                "version {} of {} is not available, available versions: {}",
+                version,
+                engine,
+                e.versions.join(", ")
+            )
+            .into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,254 @@
+//! High-level access to the Public Cloud Object Storage (S3) API.
+
+use crate::client::OvhClient;
+
+use serde::{Deserialize, Serialize};
+
+/// Structure representing a Public Cloud user.
+///
+/// Object Storage access is granted to users holding the
+/// `objectstore_operator` role, through S3 credentials and policies.
+#[derive(Debug, Deserialize)]
+pub struct OvhCloudUser {
+    /// Unique identifier of the user
+    pub id: u64,
+    /// Name of the user, generated by OVH
+    pub username: String,
+    /// Description of the user
+    pub description: String,
+    /// Current status of the user (`creating`, `ok`...)
+    pub status: String,
+    /// Password of the user, only returned on creation
+    pub password: Option<String>,
+}
+
+/// Structure representing S3 credentials of a user.
+#[derive(Debug, Deserialize)]
+pub struct OvhS3Credentials {
+    /// Access key
+    pub access: String,
+    /// Secret key, only returned on creation
+    pub secret: Option<String>,
+    /// User owning the credentials
+    #[serde(rename(deserialize = "userId"))]
+    pub user_id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhCloudUserCreate<'a> {
+    description: &'a str,
+    role: &'a str,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OvhS3Policy {
+    policy: String,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhBucketRole<'a> {
+    #[serde(rename(serialize = "roleName"))]
+    role_name: &'a str,
+}
+
+impl OvhCloudUser {
+    /// Lists all of the users of a project.
+    pub async fn list(
+        c: &OvhClient,
+        project: &str,
+    ) -> Result<Vec<OvhCloudUser>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/cloud/project/{}/user", project))
+            .await?;
+        Ok(res)
+    }
+
+    /// Creates a new user with the given role, such as
+    /// `objectstore_operator`.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::cloud_storage::{OvhCloudUser, OvhS3Credentials};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let user = OvhCloudUser::create(&c, "project_id", "backups", "objectstore_operator")
+    ///         .await
+    ///         .unwrap();
+    ///     let creds = OvhS3Credentials::create(&c, "project_id", user.id)
+    ///         .await
+    ///         .unwrap();
+    ///     println!("{}", creds.aws_config("backups", "GRA").unwrap());
+    /// }
+    /// ```
+    pub async fn create(
+        c: &OvhClient,
+        project: &str,
+        description: &str,
+        role: &str,
+    ) -> Result<OvhCloudUser, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhCloudUserCreate { description, role };
+        let res = c
+            .post_json(&format!("/cloud/project/{}/user", project), &data)
+            .await?;
+        Ok(res)
+    }
+
+    /// Deletes a user, along with its S3 credentials.
+    pub async fn delete(
+        c: &OvhClient,
+        project: &str,
+        user_id: u64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.delete(&format!("/cloud/project/{}/user/{}", project, user_id))
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Retrieves the S3 policy of a user, as a JSON document.
+    pub async fn policy(
+        c: &OvhClient,
+        project: &str,
+        user_id: u64,
+    ) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json::<OvhS3Policy>(&format!(
+                "/cloud/project/{}/user/{}/policy",
+                project, user_id
+            ))
+            .await?;
+        Ok(serde_json::from_str(&res.policy)?)
+    }
+
+    /// Replaces the S3 policy of a user.
+    ///
+    /// The policy uses the AWS IAM JSON syntax.
+    pub async fn set_policy(
+        c: &OvhClient,
+        project: &str,
+        user_id: u64,
+        policy: &serde_json::Value,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhS3Policy {
+            policy: policy.to_string(),
+        };
+        c.post(
+            &format!("/cloud/project/{}/user/{}/policy", project, user_id),
+            &data,
+        )
+        .await?
+        .error_for_status()?;
+        Ok(())
+    }
+
+    /// Grants a user a predefined role on a single bucket.
+    ///
+    /// Roles are `admin`, `readOnly`, `readWrite` and `deny`.
+    pub async fn set_bucket_role(
+        c: &OvhClient,
+        project: &str,
+        region: &str,
+        bucket: &str,
+        user_id: u64,
+        role: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhBucketRole { role_name: role };
+        c.post(
+            &format!(
+                "/cloud/project/{}/region/{}/storage/{}/policy/{}",
+                project, region, bucket, user_id
+            ),
+            &data,
+        )
+        .await?
+        .error_for_status()?;
+        Ok(())
+    }
+}
+
+impl OvhS3Credentials {
+    /// Creates new S3 credentials for a user.
+    pub async fn create(
+        c: &OvhClient,
+        project: &str,
+        user_id: u64,
+    ) -> Result<OvhS3Credentials, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .post_json(
+                &format!("/cloud/project/{}/user/{}/s3Credentials", project, user_id),
+                &serde_json::json!({}),
+            )
+            .await?;
+        Ok(res)
+    }
+
+    /// Lists all of the S3 credentials of a user, without their secret.
+    pub async fn list(
+        c: &OvhClient,
+        project: &str,
+        user_id: u64,
+    ) -> Result<Vec<OvhS3Credentials>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/cloud/project/{}/user/{}/s3Credentials",
+                project, user_id
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Revokes S3 credentials.
+    pub async fn delete(
+        c: &OvhClient,
+        project: &str,
+        user_id: u64,
+        access: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.delete(&format!(
+            "/cloud/project/{}/user/{}/s3Credentials/{}",
+            project, user_id, access
+        ))
+        .await?
+        .error_for_status()?;
+        Ok(())
+    }
+
+    /// Formats the credentials as a profile of the AWS CLI configuration
+    /// file (`~/.aws/config`), also understood by the AWS SDKs.
+    ///
+    /// Returns `None` if the secret key is unknown, which is the case for
+    /// credentials that were not just created.
+    ///
+    /// ```
+    /// use ovh::cloud_storage::OvhS3Credentials;
+    ///
+    /// let creds: OvhS3Credentials = serde_json::from_str(
+    ///     r#"{"access": "AK", "secret": "SK", "userId": "42"}"#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     creds.aws_config("backups", "GRA").unwrap(),
+    ///     "[profile backups]\n\
+    ///      aws_access_key_id = AK\n\
+    ///      aws_secret_access_key = SK\n\
+    ///      region = gra\n\
+    ///      endpoint_url = https://s3.gra.io.cloud.ovh.net\n"
+    /// );
+    /// ```
+    pub fn aws_config(&self, profile: &str, region: &str) -> Option<String> {
+        let secret = self.secret.as_ref()?;
+        let region = region.to_lowercase();
+
+        Some(format!(
+            "[profile {}]\n\
+             aws_access_key_id = {}\n\
+             aws_secret_access_key = {}\n\
+             region = {}\n\
+             endpoint_url = https://s3.{}.io.cloud.ovh.net\n",
+            profile, self.access, secret, region, region
+        ))
+    }
+}
@@ -0,0 +1,121 @@
+//! Coalescing of identical GET requests in flight.
+
+use std::{collections::HashMap, future::Future, sync::Mutex};
+
+use reqwest::{header::HeaderMap, Response, StatusCode};
+use tokio::sync::oneshot;
+
+use crate::cache::rebuild;
+
+/// Response shared with the callers waiting for the same request.
+type SharedResponse = (StatusCode, HeaderMap, Vec<u8>);
+
+/// Coalescer of the GET requests sent concurrently to the same URL.
+///
+/// While a GET request is in flight, other GET requests to the same URL
+/// do not reach the API: they wait for the first one to complete and get
+/// a copy of its response. This cuts duplicate traffic in services where
+/// many tasks ask for the same resource at once, such as the server time
+/// when they all start. If the first request fails, the waiting ones are
+/// sent on their own.
+///
+/// ```
+/// use ovh::client::OvhClient;
+/// use ovh::coalesce::RequestCoalescer;
+///
+/// let client = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key")
+///     .unwrap()
+///     .with_request_coalescing(RequestCoalescer::new());
+/// ```
+#[derive(Debug, Default)]
+pub struct RequestCoalescer {
+    in_flight: Mutex<HashMap<String, Vec<oneshot::Sender<SharedResponse>>>>,
+}
+
+impl RequestCoalescer {
+    /// Creates a coalescer without any request in flight.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of distinct requests in flight.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.lock().unwrap().len()
+    }
+
+    /// Sends a request with `send`, unless a request with the same key is
+    /// already in flight, in which case its response is awaited instead.
+    pub(crate) async fn run<F, Fut>(
+        &self,
+        key: &str,
+        send: F,
+    ) -> Result<Response, Box<dyn std::error::Error + Send + Sync>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<Response, Box<dyn std::error::Error + Send + Sync>>>,
+    {
+        let waiting = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get_mut(key) {
+                Some(waiters) => {
+                    let (tx, rx) = oneshot::channel();
+                    waiters.push(tx);
+                    Some(rx)
+                }
+                None => {
+                    in_flight.insert(key.into(), Vec::new());
+                    None
+                }
+            }
+        };
+
+        if let Some(rx) = waiting {
+            // The sender is dropped without a response when the first
+            // request fails or is cancelled.
+            return match rx.await {
+                Ok((status, headers, body)) => rebuild(status, headers, body),
+                Err(_) => send().await,
+            };
+        }
+
+        // Removes the entry even if this future is dropped, so that the
+        // waiters are released.
+        let mut guard = InFlight {
+            coalescer: self,
+            key,
+            done: false,
+        };
+        let resp = send().await?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let body = resp.bytes().await?.to_vec();
+        for tx in guard.take_waiters() {
+            let _ = tx.send((status, headers.clone(), body.clone()));
+        }
+        rebuild(status, headers, body)
+    }
+}
+
+struct InFlight<'a> {
+    coalescer: &'a RequestCoalescer,
+    key: &'a str,
+    done: bool,
+}
+
+impl InFlight<'_> {
+    /// Removes the entry of the request, returning its waiters.
+    fn take_waiters(&mut self) -> Vec<oneshot::Sender<SharedResponse>> {
+        self.done = true;
+        let mut in_flight = self.coalescer.in_flight.lock().unwrap();
+        in_flight.remove(self.key).unwrap_or_default()
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        // Once done, the key may already belong to a newer request.
+        if !self.done {
+            self.take_waiters();
+        }
+    }
+}
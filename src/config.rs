@@ -0,0 +1,261 @@
+//! Settings of a client, independent of where they are read from.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use configparser::ini::Ini;
+use secrecy::zeroize::Zeroize;
+use serde::{Deserialize, Serialize};
+
+use crate::endpoint::Endpoint;
+use crate::secret::SecretProvider;
+
+/// Settings needed to build a client.
+///
+/// The fields are named like the keys of `ovh.conf`, so the settings can
+/// be deserialized from any format supported by serde, such as TOML or a
+/// layered configuration assembled by crates like `figment` or `config`,
+/// and handed to
+/// [`OvhClient::from_config`](crate::client::OvhClient::from_config).
+///
+/// ```
+/// use ovh::client::OvhClient;
+/// use ovh::config::OvhConfig;
+///
+/// let config: OvhConfig = serde_json::from_str(
+///     r#"{
+///         "endpoint": "ovh-eu",
+///         "application_key": "app_key",
+///         "application_secret": "app_secret",
+///         "consumer_key": "consumer_key"
+///     }"#,
+/// )
+/// .unwrap();
+/// let client = OvhClient::from_config(&config).unwrap();
+/// assert_eq!(client.endpoint_name(), "ovh-eu");
+/// ```
+///
+/// The application secret and the consumer key are redacted from the
+/// `Debug` output, and wiped from memory when the settings are dropped.
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OvhConfig {
+    /// Endpoint targeted by the client
+    pub endpoint: Endpoint,
+    /// Application key
+    pub application_key: String,
+    /// Application secret
+    pub application_secret: String,
+    /// Consumer key, empty when only requesting a new one
+    #[serde(default)]
+    pub consumer_key: String,
+    /// URL of the proxy requests are sent through, instead of the one set
+    /// by the proxy environment variables
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+}
+
+impl fmt::Debug for OvhConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OvhConfig")
+            .field("endpoint", &self.endpoint)
+            .field("application_key", &self.application_key)
+            .field("application_secret", &"[REDACTED]")
+            .field("consumer_key", &"[REDACTED]")
+            .field("proxy", &self.proxy)
+            .finish()
+    }
+}
+
+impl Drop for OvhConfig {
+    fn drop(&mut self) {
+        self.application_secret.zeroize();
+        self.consumer_key.zeroize();
+    }
+}
+
+impl OvhConfig {
+    /// Creates settings from the credentials of an application.
+    ///
+    /// ```
+    /// use ovh::config::OvhConfig;
+    /// use ovh::endpoint::Endpoint;
+    ///
+    /// let config = OvhConfig::new(Endpoint::OvhCa, "app_key", "app_secret", "consumer_key");
+    /// assert!(config.proxy.is_none());
+    /// ```
+    pub fn new(
+        endpoint: Endpoint,
+        application_key: &str,
+        application_secret: &str,
+        consumer_key: &str,
+    ) -> Self {
+        Self {
+            endpoint,
+            application_key: application_key.into(),
+            application_secret: application_secret.into(),
+            consumer_key: consumer_key.into(),
+            proxy: None,
+        }
+    }
+
+    /// Sets the URL of the proxy requests are sent through.
+    pub fn with_proxy(mut self, url: &str) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Reads the settings from a configuration file in the format of
+    /// [python-ovh](https://github.com/ovh/python-ovh), as described in
+    /// [`OvhClient::from_conf`](crate::client::OvhClient::from_conf).
+    ///
+    /// This is the same as [`from_ini_profile`](Self::from_ini_profile)
+    /// with the `default` profile.
+    pub fn from_ini<T>(path: T) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: AsRef<Path>,
+    {
+        Self::from_ini_profile(path, "default")
+    }
+
+    /// Reads the settings of a profile from a configuration file in the
+    /// format of [python-ovh](https://github.com/ovh/python-ovh).
+    ///
+    /// Like python-ovh, keys missing from the file are looked up in
+    /// `~/.ovh.conf`, then in `/etc/ovh.conf`, and the file itself may be
+    /// missing as long as one of those exists.
+    ///
+    /// So that credentials can be injected by an orchestrator, values may
+    /// reference environment variables as `${NAME}`, and a key may be
+    /// replaced by a `<key>_file` one giving the path of a file holding
+    /// the value, such as `consumer_key_file=/run/secrets/ck`. Reading
+    /// fails if a variable is not set or a file cannot be read.
+    ///
+    /// The endpoint of a profile is the `endpoint` key of its section, or
+    /// the name of the profile if it is an endpoint name. The keys are
+    /// read from the section of the profile, then from the section of its
+    /// endpoint:
+    ///
+    /// ```ini
+    /// [default]
+    /// endpoint=ovh-eu
+    ///
+    /// [ovh-eu]
+    /// application_key=my_app_key
+    /// application_secret=my_application_secret
+    /// consumer_key=my_consumer_key
+    ///
+    /// [staging]
+    /// endpoint=ovh-eu
+    /// consumer_key=my_staging_consumer_key
+    /// ```
+    pub fn from_ini_profile<T>(
+        path: T,
+        profile: &str,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: AsRef<Path>,
+    {
+        let mut paths = vec![path.as_ref().to_path_buf()];
+        if let Some(home) = std::env::var_os("HOME") {
+            paths.push(PathBuf::from(home).join(".ovh.conf"));
+        }
+        paths.push(PathBuf::from("/etc/ovh.conf"));
+
+        // Highest priority first.
+        let mut confs = Vec::new();
+        for p in &paths {
+            if !p.is_file() {
+                continue;
+            }
+            let mut conf = Ini::new();
+            conf.load(p)
+                .map_err(|e| format!("{}: {}", p.display(), e))?;
+            confs.push(conf);
+        }
+        if confs.is_empty() {
+            return Err(format!("{}: no such file", path.as_ref().display()).into());
+        }
+        let lookup = |section: &str, key: &str| confs.iter().find_map(|c| c.get(section, key));
+
+        let is_endpoint = profile != "default" && profile.parse::<Endpoint>().is_ok();
+        let endpoint = match lookup(profile, "endpoint") {
+            Some(e) => interpolate("endpoint", &e)?,
+            None if is_endpoint => profile.into(),
+            None => {
+                let msg = format!("missing key `endpoint` in profile `{}`", profile);
+                return Err(msg.into());
+            }
+        };
+        let get = |key: &str| -> Result<Option<String>, String> {
+            if let Some(v) = lookup(profile, key).or_else(|| lookup(&endpoint, key)) {
+                return interpolate(key, &v).map(Some);
+            }
+            let file_key = format!("{}_file", key);
+            match lookup(profile, &file_key).or_else(|| lookup(&endpoint, &file_key)) {
+                Some(p) => read_secret_file(&file_key, &interpolate(&file_key, &p)?).map(Some),
+                None => Ok(None),
+            }
+        };
+        let require = |key: &str| get(key)?.ok_or_else(|| format!("missing key `{}`", key));
+        let proxy = match lookup(profile, "proxy").or_else(|| lookup("default", "proxy")) {
+            Some(p) => Some(interpolate("proxy", &p)?),
+            None => None,
+        };
+
+        Ok(Self {
+            endpoint: endpoint.parse()?,
+            application_key: require("application_key")?,
+            application_secret: require("application_secret")?,
+            consumer_key: require("consumer_key")?,
+            proxy,
+        })
+    }
+
+    /// Reads the settings from a secret provider, such as
+    /// [`EnvProvider`](crate::secret::EnvProvider) for the `OVH_*`
+    /// environment variables.
+    ///
+    /// The `proxy` key is optional, the other ones are required.
+    pub fn from_provider<P>(provider: &P) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        P: SecretProvider + ?Sized,
+    {
+        Ok(Self {
+            endpoint: provider.require("endpoint")?.parse()?,
+            application_key: provider.require("application_key")?,
+            application_secret: provider.require("application_secret")?,
+            consumer_key: provider.require("consumer_key")?,
+            proxy: provider.get("proxy")?,
+        })
+    }
+}
+
+/// Replaces the `${NAME}` references to environment variables in the value
+/// of a key.
+fn interpolate(key: &str, value: &str) -> Result<String, String> {
+    let mut res = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        res.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("`{}`: unterminated `${{` in value", key))?;
+        let name = &rest[start + 2..start + end];
+        let var =
+            std::env::var(name).map_err(|e| format!("`{}`: variable `{}`: {}", key, name, e))?;
+        res.push_str(&var);
+        rest = &rest[start + end + 1..];
+    }
+    res.push_str(rest);
+    Ok(res)
+}
+
+/// Reads a value from a file, such as a secret mounted by an orchestrator,
+/// without its trailing newline.
+fn read_secret_file(key: &str, path: &str) -> Result<String, String> {
+    let contents =
+        std::fs::read_to_string(path).map_err(|e| format!("`{}`: {}: {}", key, path, e))?;
+    Ok(contents.trim_end_matches(&['\r', '\n'][..]).to_string())
+}
@@ -0,0 +1,123 @@
+//! High-level access to the dedicated housing (colocation) API.
+
+use crate::client::OvhClient;
+
+use serde::Deserialize;
+
+/// Structure representing a housing bay.
+#[derive(Debug, Deserialize)]
+pub struct OvhDedicatedHousing {
+    /// Name of the housing service
+    pub name: String,
+    /// Datacenter hosting the bay
+    pub datacenter: String,
+    /// Rack of the bay
+    pub rack: String,
+    /// Code to give to the datacenter staff to access the bay
+    #[serde(rename(deserialize = "securityCode"))]
+    pub security_code: Option<String>,
+}
+
+/// Structure representing an asynchronous task on a housing bay.
+#[derive(Debug, Deserialize)]
+pub struct OvhDedicatedHousingTask {
+    /// Unique identifier of the task
+    #[serde(rename(deserialize = "taskId"))]
+    pub task_id: u64,
+    /// Kind of operation performed by the task, empty if the endpoint does
+    /// not tell it
+    #[serde(default)]
+    pub function: String,
+    /// Current status of the task (`todo`, `doing`, `done`, `error`...)
+    pub status: String,
+    /// Details about the task
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OvhOrderable {
+    orderable: bool,
+}
+
+impl OvhDedicatedHousing {
+    /// Retrieves a housing bay.
+    pub async fn get(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<OvhDedicatedHousing, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/dedicated/housing/{}", name)).await?;
+        Ok(res)
+    }
+
+    /// Lists all of the housing bays of the account.
+    ///
+    /// This method will perform one extra API call per bay
+    /// in order to get their details.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dedicated_housing::OvhDedicatedHousing;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     for h in OvhDedicatedHousing::list(&c).await.unwrap() {
+    ///         println!("{}: {} rack {}", h.name, h.datacenter, h.rack);
+    ///     }
+    /// }
+    /// ```
+    pub async fn list(
+        c: &OvhClient,
+    ) -> Result<Vec<OvhDedicatedHousing>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json::<Vec<String>>("/dedicated/housing").await?;
+        let res: Vec<_> =
+            futures::future::join_all(res.iter().map(|name| Self::get(c, name))).await;
+
+        let res = res.into_iter().filter_map(|c| c.ok()).collect();
+
+        Ok(res)
+    }
+
+    /// Checks whether an APC (remotely controlled power distribution unit)
+    /// can be ordered for a housing bay.
+    pub async fn apc_orderable(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json::<OvhOrderable>(&format!("/dedicated/housing/{}/orderable/APC", name))
+            .await?;
+        Ok(res.orderable)
+    }
+
+    /// Retrieves a task of a housing bay.
+    pub async fn task(
+        c: &OvhClient,
+        name: &str,
+        task_id: u64,
+    ) -> Result<OvhDedicatedHousingTask, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/dedicated/housing/{}/task/{}", name, task_id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Lists all of the tasks of a housing bay.
+    ///
+    /// This method will perform one extra API call per task
+    /// in order to get their details.
+    pub async fn tasks(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<Vec<OvhDedicatedHousingTask>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json::<Vec<u64>>(&format!("/dedicated/housing/{}/task", name))
+            .await?;
+        let res: Vec<_> =
+            futures::future::join_all(res.iter().map(|&id| Self::task(c, name, id))).await;
+
+        let res = res.into_iter().filter_map(|c| c.ok()).collect();
+
+        Ok(res)
+    }
+}
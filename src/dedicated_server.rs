@@ -0,0 +1,198 @@
+//! High-level access to the dedicated server API.
+
+use crate::client::OvhClient;
+
+use serde::{Deserialize, Serialize};
+
+/// Structure representing a dedicated server.
+#[derive(Debug, Deserialize)]
+pub struct OvhDedicatedServer {
+    /// Name of the server
+    pub name: String,
+    /// Main IP address of the server
+    pub ip: Option<String>,
+    /// Reverse DNS of the main address
+    pub reverse: Option<String>,
+    /// Datacenter hosting the server
+    pub datacenter: Option<String>,
+    /// State of the server (`ok`, `hacked`, `error`...)
+    pub state: Option<String>,
+}
+
+/// Structure representing a virtual network interface of a server.
+///
+/// Virtual network interfaces are what OLA (OVHcloud Link Aggregation)
+/// groups together: each one is backed by one or more physical network
+/// controllers.
+#[derive(Debug, Deserialize)]
+pub struct OvhDedicatedServerVni {
+    /// Unique identifier of the interface
+    pub uuid: String,
+    /// Name of the interface
+    pub name: String,
+    /// Mode of the interface (`public`, `vrack`, `vrack_aggregation`...)
+    pub mode: String,
+    /// vRack the interface is attached to, if any
+    pub vrack: Option<String>,
+    /// MAC addresses of the physical controllers behind the interface
+    #[serde(rename(deserialize = "networkInterfaceController"))]
+    pub network_interface_controller: Vec<String>,
+    /// Whether the interface is enabled
+    pub enabled: bool,
+}
+
+/// Structure representing an asynchronous task on a server.
+#[derive(Debug, Deserialize)]
+pub struct OvhDedicatedServerTask {
+    /// Unique identifier of the task
+    #[serde(rename(deserialize = "taskId"))]
+    pub task_id: u64,
+    /// Kind of operation performed by the task, empty if the endpoint does
+    /// not tell it
+    #[serde(default)]
+    pub function: String,
+    /// Current status of the task (`todo`, `doing`, `done`, `error`...)
+    pub status: String,
+    /// Details about the task
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhOlaAggregation<'a> {
+    name: &'a str,
+
+    #[serde(rename(serialize = "virtualNetworkInterfaces"))]
+    virtual_network_interfaces: &'a [&'a str],
+}
+
+#[derive(Debug, Serialize)]
+struct OvhOlaReset<'a> {
+    #[serde(rename(serialize = "virtualNetworkInterface"))]
+    virtual_network_interface: &'a str,
+}
+
+impl OvhDedicatedServer {
+    /// Retrieves a dedicated server.
+    pub async fn get(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<OvhDedicatedServer, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/dedicated/server/{}", name)).await?;
+        Ok(res)
+    }
+
+    /// Lists the names of all of the dedicated servers of the account.
+    pub async fn list_names(
+        c: &OvhClient,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json("/dedicated/server").await?;
+        Ok(res)
+    }
+
+    /// Lists the IP blocks attached to a server, in CIDR notation.
+    pub async fn ips(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/dedicated/server/{}/ips", name))
+            .await?;
+        Ok(res)
+    }
+}
+
+impl OvhDedicatedServerVni {
+    /// Retrieves a virtual network interface.
+    async fn get_vni(
+        c: &OvhClient,
+        server: &str,
+        uuid: &str,
+    ) -> Result<OvhDedicatedServerVni, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/dedicated/server/{}/virtualNetworkInterface/{}",
+                server, uuid
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Lists all of the virtual network interfaces of a server.
+    ///
+    /// This method will perform one extra API call per interface
+    /// in order to get their details.
+    pub async fn list(
+        c: &OvhClient,
+        server: &str,
+    ) -> Result<Vec<OvhDedicatedServerVni>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json::<Vec<String>>(&format!(
+                "/dedicated/server/{}/virtualNetworkInterface",
+                server
+            ))
+            .await?;
+        let res: Vec<_> =
+            futures::future::join_all(res.iter().map(|uuid| Self::get_vni(c, server, uuid))).await;
+
+        let res = res.into_iter().filter_map(|c| c.ok()).collect();
+
+        Ok(res)
+    }
+
+    /// Groups several interfaces into a single aggregated interface.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dedicated_server::OvhDedicatedServerVni;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let vnis: Vec<_> = OvhDedicatedServerVni::list(&c, "ns1234.ip-1-2-3.eu")
+    ///         .await
+    ///         .unwrap()
+    ///         .into_iter()
+    ///         .map(|v| v.uuid)
+    ///         .collect();
+    ///     let vnis: Vec<_> = vnis.iter().map(String::as_str).collect();
+    ///
+    ///     let task = OvhDedicatedServerVni::ola_group(&c, "ns1234.ip-1-2-3.eu", "bond0", &vnis)
+    ///         .await
+    ///         .unwrap();
+    ///     println!("{}", task.task_id);
+    /// }
+    /// ```
+    pub async fn ola_group(
+        c: &OvhClient,
+        server: &str,
+        name: &str,
+        uuids: &[&str],
+    ) -> Result<OvhDedicatedServerTask, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhOlaAggregation {
+            name,
+            virtual_network_interfaces: uuids,
+        };
+        let res = c
+            .post_json(
+                &format!("/dedicated/server/{}/ola/aggregation", server),
+                &data,
+            )
+            .await?;
+        Ok(res)
+    }
+
+    /// Splits an aggregated interface back into its individual interfaces.
+    pub async fn ola_ungroup(
+        c: &OvhClient,
+        server: &str,
+        uuid: &str,
+    ) -> Result<OvhDedicatedServerTask, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhOlaReset {
+            virtual_network_interface: uuid,
+        };
+        let res = c
+            .post_json(&format!("/dedicated/server/{}/ola/reset", server), &data)
+            .await?;
+        Ok(res)
+    }
+}
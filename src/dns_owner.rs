@@ -0,0 +1,195 @@
+//! Ownership of DNS records shared with other tools.
+//!
+//! Like [external-dns](https://github.com/kubernetes-sigs/external-dns),
+//! a [`TxtRegistry`] marks every set of records it writes with a companion
+//! TXT record naming its owner, and refuses to modify sets owned by
+//! someone else or created by hand. Several tools, or several instances of
+//! the same tool, can then manage records of the same zone.
+
+use crate::client::OvhClient;
+use crate::dns_record::{DnsRecordType, OvhDnsRecord};
+use crate::dns_zone::{OvhDnsZone, OvhRrset};
+
+/// Default prefix of the subdomains of the ownership markers.
+pub const DEFAULT_PREFIX: &str = "_rust-ovh-";
+
+/// Registry of the record sets owned by a given owner, tracked through TXT
+/// markers.
+///
+/// The marker of the `A` records of `www` is the TXT record of
+/// `_rust-ovh-a.www`, whose target is
+/// `"heritage=rust-ovh,rust-ovh/owner=<owner id>"`.
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::dns_owner::TxtRegistry;
+/// use ovh::dns_record::DnsRecordType;
+/// use ovh::dns_zone::OvhDnsZone;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let registry = TxtRegistry::new("cluster-1");
+///     registry
+///         .set_rrset(&c, "example.com", "www", DnsRecordType::A, &["203.0.113.1"], None)
+///         .await
+///         .unwrap();
+///     OvhDnsZone::refresh(&c, "example.com").await.unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TxtRegistry {
+    owner_id: String,
+    prefix: String,
+}
+
+impl TxtRegistry {
+    /// Creates a registry for the given owner, which must be unique among
+    /// the tools managing the zone.
+    pub fn new(owner_id: &str) -> Self {
+        Self {
+            owner_id: owner_id.into(),
+            prefix: DEFAULT_PREFIX.into(),
+        }
+    }
+
+    /// Sets the prefix of the subdomains of the markers, `_rust-ovh-` by
+    /// default.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Returns the owner of the registry.
+    pub fn owner_id(&self) -> &str {
+        &self.owner_id
+    }
+
+    /// Returns the subdomain of the marker of a set of records.
+    ///
+    /// ```
+    /// use ovh::dns_owner::TxtRegistry;
+    /// use ovh::dns_record::DnsRecordType;
+    ///
+    /// let registry = TxtRegistry::new("cluster-1");
+    /// assert_eq!(registry.marker_sub_domain("www", DnsRecordType::A), "_rust-ovh-a.www");
+    /// assert_eq!(registry.marker_sub_domain("", DnsRecordType::Mx), "_rust-ovh-mx");
+    /// assert_eq!(
+    ///     registry.marker_sub_domain("*.dev", DnsRecordType::Cname),
+    ///     "_rust-ovh-cname._wildcard.dev"
+    /// );
+    /// ```
+    pub fn marker_sub_domain(&self, sub_domain: &str, field_type: DnsRecordType) -> String {
+        let label = format!("{}{}", self.prefix, field_type.as_str().to_lowercase());
+        match sub_domain {
+            "" => label,
+            // A wildcard label can only be the leftmost one.
+            s => format!("{}.{}", label, s.replacen('*', "_wildcard", 1)),
+        }
+    }
+
+    /// Returns the target of the markers of the registry.
+    pub fn marker_target(&self) -> String {
+        format!("\"heritage=rust-ovh,rust-ovh/owner={}\"", self.owner_id)
+    }
+
+    /// Returns the owner of a set of records, read from its marker, or
+    /// `None` if it has no marker.
+    pub async fn owner(
+        &self,
+        c: &OvhClient,
+        zone: &str,
+        sub_domain: &str,
+        field_type: DnsRecordType,
+    ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let marker = self.marker_sub_domain(sub_domain, field_type);
+        let records =
+            OvhDnsRecord::list_by_sub_domain(c, zone, &marker, Some(DnsRecordType::Txt)).await?;
+        Ok(records.iter().find_map(|r| parse_owner(&r.target)))
+    }
+
+    /// Replaces the records of a subdomain and type, like
+    /// [`OvhDnsZone::set_rrset`], and marks them as owned by the registry.
+    ///
+    /// Fails without modifying anything if records exist and are not owned
+    /// by the registry. An empty list of targets deletes the set and its
+    /// marker. The zone must be refreshed for the changes to be served.
+    pub async fn set_rrset(
+        &self,
+        c: &OvhClient,
+        zone: &str,
+        sub_domain: &str,
+        field_type: DnsRecordType,
+        targets: &[&str],
+        ttl: Option<u32>,
+    ) -> Result<OvhRrset, Box<dyn std::error::Error + Send + Sync>> {
+        match self.owner(c, zone, sub_domain, field_type).await? {
+            Some(owner) if owner == self.owner_id => (),
+            Some(owner) => {
+                let msg = format!(
+                    "{} records of `{}` in {} are owned by `{}`",
+                    field_type, sub_domain, zone, owner
+                );
+                return Err(msg.into());
+            }
+            None => {
+                let existing =
+                    OvhDnsRecord::list_by_sub_domain(c, zone, sub_domain, Some(field_type)).await?;
+                if !existing.is_empty() {
+                    let msg = format!(
+                        "{} records of `{}` in {} are not managed by `{}`",
+                        field_type, sub_domain, zone, self.owner_id
+                    );
+                    return Err(msg.into());
+                }
+            }
+        }
+
+        let marker = self.marker_sub_domain(sub_domain, field_type);
+        let marker_target = self.marker_target();
+        if targets.is_empty() {
+            let set = OvhDnsZone::set_rrset(c, zone, sub_domain, field_type, targets, ttl).await?;
+            OvhDnsZone::set_rrset(c, zone, &marker, DnsRecordType::Txt, &[], None).await?;
+            Ok(set)
+        } else {
+            // The marker is written first, so that the records are never
+            // left without owner.
+            OvhDnsZone::set_rrset(c, zone, &marker, DnsRecordType::Txt, &[&marker_target], ttl)
+                .await?;
+            OvhDnsZone::set_rrset(c, zone, sub_domain, field_type, targets, ttl).await
+        }
+    }
+
+    /// Lists the sets of records of a zone owned by the registry, markers
+    /// excluded.
+    pub async fn owned_rrsets(
+        &self,
+        c: &OvhClient,
+        zone: &str,
+    ) -> Result<Vec<OvhRrset>, Box<dyn std::error::Error + Send + Sync>> {
+        let sets = OvhDnsZone::rrsets(c, zone).await?;
+        let is_owned = |set: &OvhRrset| {
+            let marker = self.marker_sub_domain(&set.sub_domain, set.field_type);
+            sets.iter().any(|m| {
+                m.sub_domain == marker
+                    && m.field_type == DnsRecordType::Txt
+                    && m.records
+                        .iter()
+                        .any(|r| parse_owner(&r.target).as_deref() == Some(&self.owner_id))
+            })
+        };
+        Ok(sets.iter().filter(|s| is_owned(s)).cloned().collect())
+    }
+}
+
+/// Parses the owner from the target of a marker.
+fn parse_owner(target: &str) -> Option<String> {
+    let target = target.trim_matches('"');
+    if !target.split(',').any(|kv| kv == "heritage=rust-ovh") {
+        return None;
+    }
+    target
+        .split(',')
+        .find_map(|kv| kv.strip_prefix("rust-ovh/owner="))
+        .map(String::from)
+}
@@ -0,0 +1,748 @@
+//! High-level access to the DNS record API.
+
+use core::fmt;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+    future::Future,
+    net::{Ipv4Addr, Ipv6Addr},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use crate::client::OvhClient;
+use crate::dns_zone::{for_each_zone, OvhDnsZone};
+use crate::fetch::fetch_all;
+
+use serde::{Deserialize, Serialize};
+
+/// Type of a DNS record.
+///
+/// Types unknown to this crate, such as ones added to the API later,
+/// deserialize as [`DnsRecordType::Unknown`], so that listing the records
+/// of a zone holding one does not fail:
+///
+/// ```
+/// use ovh::dns_record::DnsRecordType;
+///
+/// let t: DnsRecordType = serde_json::from_str(r#""OPENPGPKEY""#).unwrap();
+/// assert_eq!(t, DnsRecordType::Unknown);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DnsRecordType {
+    /// IPv4 address
+    A,
+    /// IPv6 address
+    Aaaa,
+    /// Certification authorities allowed to issue certificates
+    Caa,
+    /// Canonical name, aliasing another domain
+    Cname,
+    /// DKIM public key, stored as TXT
+    Dkim,
+    /// DMARC policy, stored as TXT
+    Dmarc,
+    /// Delegation of a whole subtree to another domain
+    Dname,
+    /// HTTPS service binding
+    Https,
+    /// Geographical location
+    Loc,
+    /// Mail exchanger
+    Mx,
+    /// Naming authority pointer
+    Naptr,
+    /// Name server
+    Ns,
+    /// Pointer, for reverse resolution
+    Ptr,
+    /// Responsible person
+    Rp,
+    /// SPF policy, stored as TXT
+    Spf,
+    /// Service locator
+    Srv,
+    /// SSH public key fingerprint
+    Sshfp,
+    /// Service binding
+    Svcb,
+    /// TLS certificate association
+    Tlsa,
+    /// Text
+    Txt,
+    /// Any other type, not supported by this crate yet
+    #[serde(other)]
+    Unknown,
+}
+
+impl DnsRecordType {
+    /// Name of the type, as used by the API, or `UNKNOWN` for
+    /// [`DnsRecordType::Unknown`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DnsRecordType::A => "A",
+            DnsRecordType::Aaaa => "AAAA",
+            DnsRecordType::Caa => "CAA",
+            DnsRecordType::Cname => "CNAME",
+            DnsRecordType::Dkim => "DKIM",
+            DnsRecordType::Dmarc => "DMARC",
+            DnsRecordType::Dname => "DNAME",
+            DnsRecordType::Https => "HTTPS",
+            DnsRecordType::Loc => "LOC",
+            DnsRecordType::Mx => "MX",
+            DnsRecordType::Naptr => "NAPTR",
+            DnsRecordType::Ns => "NS",
+            DnsRecordType::Ptr => "PTR",
+            DnsRecordType::Rp => "RP",
+            DnsRecordType::Spf => "SPF",
+            DnsRecordType::Srv => "SRV",
+            DnsRecordType::Sshfp => "SSHFP",
+            DnsRecordType::Svcb => "SVCB",
+            DnsRecordType::Tlsa => "TLSA",
+            DnsRecordType::Txt => "TXT",
+            DnsRecordType::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl Display for DnsRecordType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Time to live applied by OVH to records whose TTL is `0`, unless the
+/// zone has been configured otherwise.
+pub const DEFAULT_TTL: u32 = 3600;
+
+/// Number of records retrieved per request by the listings of records.
+pub const BATCH_SIZE: usize = 50;
+
+/// Maximum number of zones searched concurrently by [`search_records`].
+const SEARCH_CONCURRENCY: usize = 4;
+
+/// Structure representing a single DNS record.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhDnsRecord {
+    /// Unique identifier of the record
+    pub id: u64,
+    /// Zone the record belongs to, empty if the endpoint does not tell it
+    #[serde(default)]
+    pub zone: String,
+    /// Subdomain of the record, empty for the zone apex
+    #[serde(rename(deserialize = "subDomain"), default)]
+    pub sub_domain: String,
+    /// Type of the record
+    #[serde(rename(deserialize = "fieldType"))]
+    pub field_type: DnsRecordType,
+    /// Value of the record
+    pub target: String,
+    /// Time to live of the record, in seconds, `0` for the default one
+    #[serde(default)]
+    pub ttl: u32,
+}
+
+impl OvhDnsRecord {
+    /// Retrieves a DNS record, or `None` if it does not exist.
+    pub async fn get(
+        c: &OvhClient,
+        zone: &str,
+        id: u64,
+    ) -> Result<Option<OvhDnsRecord>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_opt(&format!("/domain/zone/{}/record/{}", zone, id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Lists the identifiers of the records of a zone, optionally restricted
+    /// to a single type and subdomain.
+    async fn list_ids(
+        c: &OvhClient,
+        zone: &str,
+        field_type: Option<DnsRecordType>,
+        sub_domain: Option<&str>,
+    ) -> Result<Vec<u64>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut params = Vec::new();
+        if let Some(t) = field_type {
+            params.push(("fieldType", t.as_str()));
+        }
+        if let Some(s) = sub_domain {
+            params.push(("subDomain", s));
+        }
+
+        let resp = c
+            .get_with_params(&format!("/domain/zone/{}/record", zone), &params)
+            .await?;
+        let res = c.read_json(resp).await?;
+        Ok(res)
+    }
+
+    /// Retrieves the details of records in batches of [`BATCH_SIZE`].
+    ///
+    /// Records deleted since they were listed are skipped, but a batch
+    /// failing as a whole fails the call, so that callers never work on a
+    /// partial view of the zone.
+    async fn get_records(
+        c: &OvhClient,
+        zone: &str,
+        ids: &[u64],
+    ) -> Result<Vec<OvhDnsRecord>, Box<dyn std::error::Error + Send + Sync>> {
+        let path = format!("/domain/zone/{}/record", zone);
+        let res = fetch_all(ids.chunks(BATCH_SIZE), c.fetch_concurrency(), |chunk| {
+            c.get_batch::<OvhDnsRecord, _>(&path, chunk, ',')
+        })
+        .await;
+
+        let mut records = Vec::new();
+        for batch in res {
+            records.extend(batch?.into_iter().filter_map(|i| i.value));
+        }
+        Ok(records)
+    }
+
+    /// Lists all of the records of a zone.
+    ///
+    /// This method will perform one extra API call per [`BATCH_SIZE`]
+    /// records in order to get their details, keeping at most
+    /// [`OvhClient::fetch_concurrency`] of them in flight.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_record::OvhDnsRecord;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let records = OvhDnsRecord::list(&c, "example.com")
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     for r in records {
+    ///        println!("{}", r);
+    ///     }
+    /// }
+    /// ```
+    pub async fn list(
+        c: &OvhClient,
+        zone: &str,
+    ) -> Result<Vec<OvhDnsRecord>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids = Self::list_ids(c, zone, None, None).await?;
+        Self::get_records(c, zone, &ids).await
+    }
+
+    /// Lists the records of a zone having one of the given types.
+    ///
+    /// One filtered query is issued per type, so only the details of
+    /// matching records are fetched. This is much cheaper than
+    /// [`OvhDnsRecord::list`] on zones holding many records of other types.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_record::{DnsRecordType, OvhDnsRecord};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let types = [DnsRecordType::A, DnsRecordType::Aaaa, DnsRecordType::Cname];
+    ///     let records = OvhDnsRecord::list_by_types(&c, "example.com", &types)
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     for r in records {
+    ///        println!("{}", r);
+    ///     }
+    /// }
+    /// ```
+    pub async fn list_by_types(
+        c: &OvhClient,
+        zone: &str,
+        types: &[DnsRecordType],
+    ) -> Result<Vec<OvhDnsRecord>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = futures::future::join_all(
+            types
+                .iter()
+                .map(|&t| Self::list_ids(c, zone, Some(t), None)),
+        )
+        .await;
+
+        let mut ids = BTreeSet::new();
+        for r in res {
+            ids.extend(r?);
+        }
+        let ids: Vec<_> = ids.into_iter().collect();
+
+        Self::get_records(c, zone, &ids).await
+    }
+
+    /// Lists the records of a subdomain of a zone, optionally restricted to
+    /// a single type.
+    ///
+    /// Use an empty subdomain for the records of the zone apex.
+    pub async fn list_by_sub_domain(
+        c: &OvhClient,
+        zone: &str,
+        sub_domain: &str,
+        field_type: Option<DnsRecordType>,
+    ) -> Result<Vec<OvhDnsRecord>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids = Self::list_ids(c, zone, field_type, Some(sub_domain)).await?;
+        Self::get_records(c, zone, &ids).await
+    }
+
+    /// Creates a new record.
+    ///
+    /// When `ttl` is `None`, the default TTL of the client is used if set
+    /// (see [`OvhClient::with_default_ttl`]), otherwise the default TTL of
+    /// the zone. The zone must be refreshed for the record to be served.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_record::{DnsRecordType, OvhDnsRecord};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap().with_default_ttl(300);
+    ///     let record = OvhDnsRecord::create(
+    ///         &c,
+    ///         "example.com",
+    ///         "www",
+    ///         DnsRecordType::A,
+    ///         "203.0.113.1",
+    ///         None,
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    ///     assert_eq!(record.ttl, 300);
+    /// }
+    /// ```
+    pub async fn create(
+        c: &OvhClient,
+        zone: &str,
+        sub_domain: &str,
+        field_type: DnsRecordType,
+        target: &str,
+        ttl: Option<u32>,
+    ) -> Result<OvhDnsRecord, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhDnsRecordCreate {
+            field_type,
+            sub_domain,
+            target,
+            ttl: ttl.or_else(|| c.default_ttl()),
+        };
+        let res = c
+            .post_json(&format!("/domain/zone/{}/record", zone), &data)
+            .await?;
+        Ok(res)
+    }
+
+    /// Creates or updates the record of a subdomain and type, then refreshes
+    /// the zone.
+    ///
+    /// If the subdomain has records of this type, the first one is updated
+    /// with `target` and `ttl`, and the other ones are left untouched; use
+    /// [`OvhDnsZone::set_rrset`] to replace them all. Otherwise, a record is
+    /// created. `ttl` is resolved as by [`OvhDnsRecord::create`], and the
+    /// TTL of an existing record is kept when it resolves to `None`.
+    /// Nothing is changed, and the zone is not refreshed, if the record is
+    /// already up to date.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_record::{DnsRecordType, OvhDnsRecord};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let record = OvhDnsRecord::upsert(
+    ///         &c,
+    ///         "example.com",
+    ///         "_acme-challenge",
+    ///         DnsRecordType::Txt,
+    ///         "token",
+    ///         Some(60),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    ///     println!("{}", record);
+    /// }
+    /// ```
+    pub async fn upsert(
+        c: &OvhClient,
+        zone: &str,
+        sub_domain: &str,
+        field_type: DnsRecordType,
+        target: &str,
+        ttl: Option<u32>,
+    ) -> Result<OvhDnsRecord, Box<dyn std::error::Error + Send + Sync>> {
+        let ttl = ttl.or_else(|| c.default_ttl());
+        let ids = Self::list_ids(c, zone, Some(field_type), Some(sub_domain)).await?;
+        let existing = match ids.iter().min() {
+            Some(&id) => Self::get(c, zone, id).await?,
+            None => None,
+        };
+
+        let record = match existing {
+            Some(r) if r.target == target && ttl.is_none_or(|ttl| ttl == r.ttl) => return Ok(r),
+            Some(mut r) => {
+                let data = OvhDnsRecordUpdate {
+                    sub_domain,
+                    target,
+                    ttl,
+                };
+                let resp = c
+                    .put(&format!("/domain/zone/{}/record/{}", zone, r.id), &data)
+                    .await?;
+                OvhClient::ensure_success(resp).await?;
+                r.target = target.into();
+                r.ttl = ttl.unwrap_or(r.ttl);
+                r
+            }
+            None => Self::create(c, zone, sub_domain, field_type, target, ttl).await?,
+        };
+
+        OvhDnsZone::refresh(c, zone).await?;
+        Ok(record)
+    }
+
+    /// Deletes a record.
+    ///
+    /// The zone must be refreshed for the deletion to be served.
+    pub async fn delete(
+        c: &OvhClient,
+        zone: &str,
+        id: u64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.delete(&format!("/domain/zone/{}/record/{}", zone, id))
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct OvhDnsRecordCreate<'a> {
+    #[serde(rename(serialize = "fieldType"))]
+    field_type: DnsRecordType,
+
+    #[serde(rename(serialize = "subDomain"))]
+    sub_domain: &'a str,
+
+    target: &'a str,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhDnsRecordUpdate<'a> {
+    #[serde(rename(serialize = "subDomain"))]
+    sub_domain: &'a str,
+
+    target: &'a str,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl: Option<u32>,
+}
+
+impl OvhDnsRecord {
+    /// Checks whether two records are semantically identical.
+    ///
+    /// Identifiers are ignored, names and host targets are compared
+    /// case-insensitively, IP addresses are compared by value, quotes
+    /// around single-string TXT-like values are ignored, and a TTL of `0`
+    /// is considered equal to [`DEFAULT_TTL`].
+    ///
+    /// ```
+    /// use ovh::dns_record::{DnsRecordType, OvhDnsRecord};
+    ///
+    /// let record = |sub_domain: &str, field_type, target: &str, ttl| OvhDnsRecord {
+    ///     id: 0,
+    ///     zone: "example.com".into(),
+    ///     sub_domain: sub_domain.into(),
+    ///     field_type,
+    ///     target: target.into(),
+    ///     ttl,
+    /// };
+    ///
+    /// let a = record("www", DnsRecordType::Cname, "Web.Example.com.", 0);
+    /// let b = record("WWW", DnsRecordType::Cname, "web.example.com.", 3600);
+    /// assert!(a.matches(&b));
+    ///
+    /// let a = record("", DnsRecordType::Aaaa, "2001:db8:0:0::1", 300);
+    /// let b = record("", DnsRecordType::Aaaa, "2001:DB8::1", 300);
+    /// assert!(a.matches(&b));
+    ///
+    /// let a = record("", DnsRecordType::Txt, "\"hello\"", 300);
+    /// let b = record("", DnsRecordType::Txt, "hello", 300);
+    /// assert!(a.matches(&b));
+    ///
+    /// let b = record("", DnsRecordType::Txt, "Hello", 300);
+    /// assert!(!a.matches(&b));
+    /// ```
+    pub fn matches(&self, other: &OvhDnsRecord) -> bool {
+        self.matches_with_ttl(other, DEFAULT_TTL)
+    }
+
+    /// Checks whether two records are semantically identical, for a zone
+    /// whose default TTL is `default_ttl`.
+    ///
+    /// See [`OvhDnsRecord::matches`] for the normalization rules.
+    pub fn matches_with_ttl(&self, other: &OvhDnsRecord, default_ttl: u32) -> bool {
+        let ttl = |r: &OvhDnsRecord| if r.ttl == 0 { default_ttl } else { r.ttl };
+
+        self.field_type == other.field_type
+            && self.zone.eq_ignore_ascii_case(&other.zone)
+            && self.sub_domain.eq_ignore_ascii_case(&other.sub_domain)
+            && ttl(self) == ttl(other)
+            && self.normalized_target() == other.normalized_target()
+    }
+
+    fn normalized_target(&self) -> String {
+        self.field_type.normalize_target(&self.target)
+    }
+}
+
+impl DnsRecordType {
+    /// Normalizes the target of a record of this type, so that targets
+    /// with the same meaning compare equal.
+    ///
+    /// See [`OvhDnsRecord::matches`] for the normalization rules.
+    pub(crate) fn normalize_target(&self, target: &str) -> String {
+        let target = target.trim();
+
+        match self {
+            DnsRecordType::A => target
+                .parse::<Ipv4Addr>()
+                .map_or_else(|_| target.into(), |ip| ip.to_string()),
+            DnsRecordType::Aaaa => target
+                .parse::<Ipv6Addr>()
+                .map_or_else(|_| target.into(), |ip| ip.to_string()),
+            DnsRecordType::Cname
+            | DnsRecordType::Dname
+            | DnsRecordType::Mx
+            | DnsRecordType::Ns
+            | DnsRecordType::Ptr
+            | DnsRecordType::Srv => target.to_lowercase(),
+            DnsRecordType::Dkim
+            | DnsRecordType::Dmarc
+            | DnsRecordType::Spf
+            | DnsRecordType::Txt => {
+                let unquoted = target
+                    .strip_prefix('"')
+                    .and_then(|t| t.strip_suffix('"'))
+                    .filter(|t| !t.contains('"'));
+                unquoted.unwrap_or(target).into()
+            }
+            _ => target.into(),
+        }
+    }
+}
+
+impl Display for OvhDnsRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} {} {} {}",
+            self.id, self.sub_domain, self.ttl, self.field_type, self.target
+        )
+    }
+}
+
+/// Registry of per-zone locks.
+///
+/// OVH rejects or silently reorders concurrent modifications of the same
+/// zone, for instance when a record is being created while the zone is
+/// refreshed. Wrapping every mutation of a zone in
+/// [`ZoneLocks::with_zone_lock`] serializes them inside the current
+/// process, while operations on different zones still run concurrently.
+#[derive(Debug, Default)]
+pub struct ZoneLocks {
+    locks: Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl ZoneLocks {
+    /// Creates an empty lock registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock_for(&self, zone: &str) -> ZoneLockEntry<'_> {
+        let zone = zone.to_lowercase();
+        let mut locks = self.locks.lock().unwrap();
+        let lock = locks.entry(zone.clone()).or_default().clone();
+        ZoneLockEntry {
+            locks: self,
+            zone,
+            lock,
+        }
+    }
+
+    /// Returns the number of zones currently locked or waited for.
+    ///
+    /// Zones are forgotten as soon as no task holds or waits for their
+    /// lock, so the registry does not grow with the number of zones ever
+    /// locked.
+    pub fn len(&self) -> usize {
+        self.locks.lock().unwrap().len()
+    }
+
+    /// Whether no zone is currently locked or waited for.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Runs `fut` while holding the lock of the given zone.
+    ///
+    /// Zone names are compared case-insensitively.
+    ///
+    /// ```
+    /// use ovh::dns_record::ZoneLocks;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let locks = ZoneLocks::new();
+    ///     let res = locks
+    ///         .with_zone_lock("example.com", async {
+    ///             assert_eq!(locks.len(), 1);
+    ///             42
+    ///         })
+    ///         .await;
+    ///     assert_eq!(res, 42);
+    ///     assert!(locks.is_empty());
+    /// }
+    /// ```
+    pub async fn with_zone_lock<F, T>(&self, zone: &str, fut: F) -> T
+    where
+        F: Future<Output = T>,
+    {
+        let entry = self.lock_for(zone);
+        let _guard = entry.lock.lock().await;
+        fut.await
+    }
+}
+
+/// Use of the lock of a zone, removing it from its registry when it was
+/// the last one.
+struct ZoneLockEntry<'a> {
+    locks: &'a ZoneLocks,
+    zone: String,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl Drop for ZoneLockEntry<'_> {
+    fn drop(&mut self) {
+        let mut locks = self.locks.locks.lock().unwrap_or_else(|e| e.into_inner());
+        // Clones are only made while holding the registry, so no other
+        // task can start using the lock once only the registry and this
+        // entry own it.
+        if Arc::strong_count(&self.lock) == 2 {
+            locks.remove(&self.zone);
+        }
+    }
+}
+
+/// Runs `fut` while holding the lock of the given zone in the process-wide
+/// registry.
+///
+/// This is a shortcut for [`ZoneLocks::with_zone_lock`] for programs that
+/// do not need several independent registries.
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::dns_record::with_zone_lock;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     with_zone_lock("example.com", async {
+///         let record = serde_json::json!({
+///             "fieldType": "A",
+///             "subDomain": "www",
+///             "target": "203.0.113.1",
+///         });
+///         c.post("/domain/zone/example.com/record", &record)
+///             .await
+///             .unwrap();
+///     })
+///     .await;
+/// }
+/// ```
+pub async fn with_zone_lock<F, T>(zone: &str, fut: F) -> T
+where
+    F: Future<Output = T>,
+{
+    static LOCKS: OnceLock<ZoneLocks> = OnceLock::new();
+    LOCKS
+        .get_or_init(ZoneLocks::new)
+        .with_zone_lock(zone, fut)
+        .await
+}
+
+/// Records found by [`search_records`].
+#[derive(Debug, Default)]
+pub struct DnsRecordSearchResult {
+    /// Matching records, grouped by zone
+    pub records: Vec<OvhDnsRecord>,
+    /// Zones that could not be searched, with the error message
+    pub failed: Vec<(String, String)>,
+}
+
+/// Searches every zone of the account for the records matching
+/// `predicate`.
+///
+/// Only the records having one of `types` are fetched, or every record if
+/// `types` is empty. Zones are searched a few at a time; a zone that could
+/// not be listed is reported in [`DnsRecordSearchResult::failed`] without
+/// stopping the search. An error is only returned if the zones of the
+/// account could not be listed.
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::dns_record::{search_records, DnsRecordType};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let res = search_records(&c, &[DnsRecordType::A], |r| r.target == "203.0.113.1")
+///         .await
+///         .unwrap();
+///
+///     for r in res.records {
+///         println!("{} {}", r.zone, r);
+///     }
+///     for (zone, e) in res.failed {
+///         eprintln!("{}: {}", zone, e);
+///     }
+/// }
+/// ```
+pub async fn search_records<F>(
+    c: &OvhClient,
+    types: &[DnsRecordType],
+    predicate: F,
+) -> Result<DnsRecordSearchResult, Box<dyn std::error::Error + Send + Sync>>
+where
+    F: Fn(&OvhDnsRecord) -> bool,
+{
+    let zones = OvhDnsZone::list_names(c).await?;
+
+    let results = for_each_zone(c, &zones, SEARCH_CONCURRENCY, |zone| async move {
+        if types.is_empty() {
+            OvhDnsRecord::list(c, &zone).await
+        } else {
+            OvhDnsRecord::list_by_types(c, &zone, types).await
+        }
+    })
+    .await;
+
+    let mut res = DnsRecordSearchResult::default();
+    for (zone, records) in results.results {
+        match records {
+            Ok(records) => res
+                .records
+                .extend(records.into_iter().filter(|r| predicate(r))),
+            Err(e) => res.failed.push((zone, e.to_string())),
+        }
+    }
+
+    Ok(res)
+}
@@ -0,0 +1,464 @@
+//! High-level access to the DNS zone API.
+
+use std::{collections::BTreeMap, future::Future};
+
+use crate::client::OvhClient;
+use crate::dns_record::{DnsRecordType, OvhDnsRecord};
+use crate::fetch::fetch_all;
+use crate::order::OvhOrder;
+
+use serde::Deserialize;
+
+/// Structure representing a DNS zone.
+#[derive(Debug, Deserialize)]
+pub struct OvhDnsZone {
+    /// Name of the zone
+    pub name: String,
+    /// Whether the zone is served by the Anycast DNS network, `false` if
+    /// the endpoint does not tell it
+    #[serde(rename(deserialize = "hasDnsAnycast"), default)]
+    pub has_dns_anycast: bool,
+    /// Whether DNSSEC can be enabled on the zone, `false` if the endpoint
+    /// does not tell it
+    #[serde(rename(deserialize = "dnssecSupported"), default)]
+    pub dnssec_supported: bool,
+    /// Name servers serving the zone, empty if the endpoint does not tell
+    /// them
+    #[serde(rename(deserialize = "nameServers"), default)]
+    pub name_servers: Vec<String>,
+}
+
+/// Structure representing the SOA record of a DNS zone.
+#[derive(Debug, Deserialize)]
+pub struct OvhDnsZoneSoa {
+    /// Primary name server of the zone
+    pub server: String,
+    /// Email address of the administrator of the zone, empty if the
+    /// endpoint does not tell it
+    #[serde(default)]
+    pub email: String,
+    /// Serial number, increased on every change to the zone
+    pub serial: u64,
+    /// Time, in seconds, after which secondary servers refresh the zone,
+    /// `0` if the endpoint does not tell it
+    #[serde(rename(deserialize = "refreshTime"), default)]
+    pub refresh_time: u64,
+    /// Time, in seconds, after which secondary servers stop answering
+    /// for the zone if they cannot refresh it, `0` if the endpoint does not
+    /// tell it
+    #[serde(default)]
+    pub expire: u64,
+    /// Time, in seconds, negative answers are cached, `0` if the endpoint
+    /// does not tell it
+    #[serde(rename(deserialize = "nxDomainTtl"), default)]
+    pub nx_domain_ttl: u64,
+    /// Default TTL of the records of the zone, `0` if the endpoint does not
+    /// tell it
+    #[serde(default)]
+    pub ttl: u64,
+}
+
+/// Structure representing the deployment status of a DNS zone.
+///
+/// ```
+/// use ovh::dns_zone::OvhDnsZoneStatus;
+///
+/// let status: OvhDnsZoneStatus = serde_json::from_str(
+///     r#"{"isDeployed": false, "errors": ["www: invalid target"], "warnings": [], "infos": []}"#,
+/// )
+/// .unwrap();
+/// assert!(!status.is_ok());
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhDnsZoneStatus {
+    /// Whether the name servers serve the current records of the zone
+    #[serde(rename(deserialize = "isDeployed"))]
+    pub is_deployed: bool,
+    /// Errors preventing the zone from being deployed, such as records
+    /// rejected at refresh
+    #[serde(default)]
+    pub errors: Vec<String>,
+    /// Warnings about the zone
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Informational messages about the zone
+    #[serde(default)]
+    pub infos: Vec<String>,
+}
+
+impl OvhDnsZoneStatus {
+    /// Whether the zone is deployed without errors.
+    pub fn is_ok(&self) -> bool {
+        self.is_deployed && self.errors.is_empty()
+    }
+}
+
+/// Set of the records of a zone sharing a subdomain and a type, which DNS
+/// resolvers always return together.
+#[derive(Debug, Clone)]
+pub struct OvhRrset {
+    /// Subdomain of the records, empty for the zone apex
+    pub sub_domain: String,
+    /// Type of the records
+    pub field_type: DnsRecordType,
+    /// The records of the set
+    pub records: Vec<OvhDnsRecord>,
+}
+
+impl OvhRrset {
+    /// Returns the targets of the records of the set.
+    pub fn targets(&self) -> Vec<&str> {
+        self.records.iter().map(|r| r.target.as_str()).collect()
+    }
+}
+
+impl OvhDnsZone {
+    /// Retrieves a DNS zone.
+    pub async fn get(
+        c: &OvhClient,
+        zone: &str,
+    ) -> Result<OvhDnsZone, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/domain/zone/{}", zone)).await?;
+        Ok(res)
+    }
+
+    /// Lists the names of all of the zones of the account.
+    pub async fn list_names(
+        c: &OvhClient,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json("/domain/zone").await?;
+        Ok(res)
+    }
+
+    /// Retrieves the SOA record of a zone.
+    pub async fn soa(
+        c: &OvhClient,
+        zone: &str,
+    ) -> Result<OvhDnsZoneSoa, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/domain/zone/{}/soa", zone)).await?;
+        Ok(res)
+    }
+
+    /// Retrieves the deployment status of a zone, telling whether the last
+    /// changes are served and why a refresh failed.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_zone::OvhDnsZone;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     OvhDnsZone::refresh(&c, "example.com").await.unwrap();
+    ///     let status = OvhDnsZone::status(&c, "example.com").await.unwrap();
+    ///     for e in &status.errors {
+    ///         eprintln!("example.com: {}", e);
+    ///     }
+    /// }
+    /// ```
+    pub async fn status(
+        c: &OvhClient,
+        zone: &str,
+    ) -> Result<OvhDnsZoneStatus, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/domain/zone/{}/status", zone)).await?;
+        Ok(res)
+    }
+
+    /// Lists the records of a zone grouped by subdomain and type, sorted by
+    /// subdomain then type.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_zone::OvhDnsZone;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     for set in OvhDnsZone::rrsets(&c, "example.com").await.unwrap() {
+    ///         println!("{} {} {:?}", set.sub_domain, set.field_type, set.targets());
+    ///     }
+    /// }
+    /// ```
+    pub async fn rrsets(
+        c: &OvhClient,
+        zone: &str,
+    ) -> Result<Vec<OvhRrset>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut sets: BTreeMap<(String, &'static str), OvhRrset> = BTreeMap::new();
+        for r in OvhDnsRecord::list(c, zone).await? {
+            sets.entry((r.sub_domain.clone(), r.field_type.as_str()))
+                .or_insert_with(|| OvhRrset {
+                    sub_domain: r.sub_domain.clone(),
+                    field_type: r.field_type,
+                    records: Vec::new(),
+                })
+                .records
+                .push(r);
+        }
+        Ok(sets.into_values().collect())
+    }
+
+    /// Replaces the records of a subdomain and type with the given targets.
+    ///
+    /// Records whose target is kept and whose TTL matches `ttl`, if given,
+    /// are left untouched, targets being compared with the normalization
+    /// rules of [`OvhDnsRecord::matches`]. The other ones are deleted, and the missing
+    /// targets are created with `ttl`, see [`OvhDnsRecord::create`]. An
+    /// empty list of targets deletes the whole set. The zone must be
+    /// refreshed for the changes to be served.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_record::DnsRecordType;
+    /// use ovh::dns_zone::OvhDnsZone;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let targets = ["203.0.113.1", "203.0.113.2"];
+    ///     OvhDnsZone::set_rrset(&c, "example.com", "www", DnsRecordType::A, &targets, Some(300))
+    ///         .await
+    ///         .unwrap();
+    ///     OvhDnsZone::refresh(&c, "example.com").await.unwrap();
+    /// }
+    /// ```
+    pub async fn set_rrset(
+        c: &OvhClient,
+        zone: &str,
+        sub_domain: &str,
+        field_type: DnsRecordType,
+        targets: &[&str],
+        ttl: Option<u32>,
+    ) -> Result<OvhRrset, Box<dyn std::error::Error + Send + Sync>> {
+        let current =
+            OvhDnsRecord::list_by_sub_domain(c, zone, sub_domain, Some(field_type)).await?;
+
+        let mut missing: Vec<(&str, String)> = Vec::new();
+        for t in targets {
+            let normalized = field_type.normalize_target(t);
+            if !missing.iter().any(|(_, n)| *n == normalized) {
+                missing.push((t, normalized));
+            }
+        }
+        let mut records = Vec::new();
+        for r in current {
+            let target = field_type.normalize_target(&r.target);
+            let wanted = missing.iter().position(|(_, n)| *n == target);
+            match wanted {
+                Some(i) if ttl.is_none_or(|ttl| ttl == r.ttl) => {
+                    missing.remove(i);
+                    records.push(r);
+                }
+                _ => OvhDnsRecord::delete(c, zone, r.id).await?,
+            }
+        }
+        for (t, _) in missing {
+            records.push(OvhDnsRecord::create(c, zone, sub_domain, field_type, t, ttl).await?);
+        }
+
+        Ok(OvhRrset {
+            sub_domain: sub_domain.into(),
+            field_type,
+            records,
+        })
+    }
+
+    /// Exports a zone in the BIND format.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_zone::OvhDnsZone;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let contents = OvhDnsZone::export(&c, "example.com").await.unwrap();
+    ///     std::fs::write("example.com.zone", contents).unwrap();
+    /// }
+    /// ```
+    pub async fn export(
+        c: &OvhClient,
+        zone: &str,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/domain/zone/{}/export", zone)).await?;
+        Ok(res)
+    }
+
+    /// Applies the pending record changes of a zone, so that they are
+    /// served by the name servers.
+    pub async fn refresh(
+        c: &OvhClient,
+        zone: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.post_empty(&format!("/domain/zone/{}/refresh", zone))
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Lists the names of the options enabled on a zone.
+    pub async fn options(
+        c: &OvhClient,
+        zone: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/domain/zone/{}/option", zone)).await?;
+        Ok(res)
+    }
+
+    /// Lists the durations the Anycast option can be ordered for.
+    pub async fn anycast_durations(
+        c: &OvhClient,
+        zone: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/order/domain/zone/{}/dnsAnycast", zone))
+            .await?;
+        Ok(res)
+    }
+
+    /// Retrieves the price of the Anycast option for a duration, without
+    /// ordering it.
+    pub async fn anycast_quote(
+        c: &OvhClient,
+        zone: &str,
+        duration: &str,
+    ) -> Result<OvhOrder, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/order/domain/zone/{}/dnsAnycast/{}",
+                zone, duration
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Orders the Anycast option for a zone.
+    ///
+    /// The returned order must then be paid for the option to be enabled.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_zone::OvhDnsZone;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let zone = OvhDnsZone::get(&c, "example.com").await.unwrap();
+    ///     if !zone.has_dns_anycast {
+    ///         let durations = OvhDnsZone::anycast_durations(&c, "example.com")
+    ///             .await
+    ///             .unwrap();
+    ///         let order = OvhDnsZone::order_anycast(&c, "example.com", &durations[0])
+    ///             .await
+    ///             .unwrap();
+    ///         println!("{} ({})", order.url.unwrap(), order.prices.with_tax.text);
+    ///     }
+    /// }
+    /// ```
+    pub async fn order_anycast(
+        c: &OvhClient,
+        zone: &str,
+        duration: &str,
+    ) -> Result<OvhOrder, Box<dyn std::error::Error + Send + Sync>> {
+        let resp = c
+            .post_empty(&format!(
+                "/order/domain/zone/{}/dnsAnycast/{}",
+                zone, duration
+            ))
+            .await?;
+        c.read_json(resp).await
+    }
+}
+
+/// Results of [`for_each_zone`], in the order of the zones.
+#[derive(Debug)]
+pub struct ZoneResults<T> {
+    /// Result of every zone, along with its name
+    pub results: Vec<(String, ZoneResult<T>)>,
+}
+
+/// Result of [`for_each_zone`] on a single zone.
+pub type ZoneResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+impl<T> ZoneResults<T> {
+    /// Whether the operation succeeded on every zone.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|(_, r)| r.is_ok())
+    }
+
+    /// Returns the zones on which the operation failed, with the error.
+    pub fn failed(&self) -> Vec<(&str, &(dyn std::error::Error + Send + Sync))> {
+        self.results
+            .iter()
+            .filter_map(|(z, r)| r.as_ref().err().map(|e| (z.as_str(), e.as_ref())))
+            .collect()
+    }
+
+    /// Returns the outputs of every zone, or a single error listing every
+    /// zone that failed.
+    pub fn into_result(self) -> Result<Vec<(String, T)>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut outputs = Vec::new();
+        let mut errors = Vec::new();
+        for (zone, r) in self.results {
+            match r {
+                Ok(t) => outputs.push((zone, t)),
+                Err(e) => errors.push(format!("{}: {}", zone, e)),
+            }
+        }
+        if errors.is_empty() {
+            Ok(outputs)
+        } else {
+            let msg = format!("{} zone(s) failed: {}", errors.len(), errors.join("; "));
+            Err(msg.into())
+        }
+    }
+}
+
+/// Runs `f` on every zone of `zones`, with at most `concurrency` zones
+/// processed at the same time, or the
+/// [fetch concurrency](OvhClient::fetch_concurrency) of the client if
+/// `concurrency` is 0.
+///
+/// A zone failing does not stop the others: every result is reported in
+/// the returned [`ZoneResults`].
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::dns_zone::{for_each_zone, OvhDnsZone};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let zones = OvhDnsZone::list_names(&c).await.unwrap();
+///     let res = for_each_zone(&c, &zones, 4, |zone| {
+///         let c = &c;
+///         async move { OvhDnsZone::refresh(c, &zone).await }
+///     })
+///     .await;
+///     for (zone, e) in res.failed() {
+///         eprintln!("{}: {}", zone, e);
+///     }
+/// }
+/// ```
+pub async fn for_each_zone<Z, F, Fut, T>(
+    c: &OvhClient,
+    zones: &[Z],
+    concurrency: usize,
+    mut f: F,
+) -> ZoneResults<T>
+where
+    Z: AsRef<str>,
+    F: FnMut(String) -> Fut,
+    Fut: Future<Output = Result<T, Box<dyn std::error::Error + Send + Sync>>>,
+{
+    let concurrency = match concurrency {
+        0 => c.fetch_concurrency(),
+        n => n,
+    };
+    let results = fetch_all(zones, concurrency, |zone| {
+        let zone = zone.as_ref().to_string();
+        let fut = f(zone.clone());
+        async move { (zone, fut.await) }
+    })
+    .await;
+    ZoneResults { results }
+}
@@ -0,0 +1,186 @@
+//! Calendar of the renewal deadlines of the domains of the account.
+//!
+//! [`DomainCalendar::collect`] gathers the expiration date and renewal mode
+//! of every domain, which can then be exported as an iCalendar feed with
+//! [`DomainCalendar::to_ics`], for teams to subscribe to, or serialized as
+//! JSON with `serde_json`.
+
+use crate::client::OvhClient;
+use crate::date::{format_day, parse_day, today};
+use crate::fetch::fetch_all;
+use crate::service::{OvhServiceInfos, OvhServiceRenew};
+
+use serde::Serialize;
+
+/// How a domain is renewed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenewMode {
+    /// Renewed automatically before it expires
+    Automatic,
+    /// Must be renewed manually before it expires
+    Manual,
+    /// Deleted when it expires
+    DeleteAtExpiration,
+}
+
+impl From<Option<&OvhServiceRenew>> for RenewMode {
+    fn from(renew: Option<&OvhServiceRenew>) -> Self {
+        match renew {
+            Some(r) if r.delete_at_expiration => RenewMode::DeleteAtExpiration,
+            Some(r) if r.automatic => RenewMode::Automatic,
+            _ => RenewMode::Manual,
+        }
+    }
+}
+
+impl std::fmt::Display for RenewMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RenewMode::Automatic => "automatic renewal",
+            RenewMode::Manual => "manual renewal",
+            RenewMode::DeleteAtExpiration => "deleted at expiration",
+        })
+    }
+}
+
+/// Renewal deadline of a domain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DomainRenewal {
+    /// Name of the domain
+    pub domain: String,
+    /// Expiration date of the domain, as reported by the API
+    pub expiration: String,
+    /// How the domain is renewed
+    pub renew_mode: RenewMode,
+}
+
+impl DomainRenewal {
+    /// Returns the number of days until the domain expires, negative if it
+    /// already has, or `None` if the expiration date cannot be read.
+    pub fn days_left(&self) -> Option<i64> {
+        Some(parse_day(&self.expiration)? - today())
+    }
+}
+
+/// Renewal deadlines of every domain of the account, the soonest first.
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::domain_calendar::DomainCalendar;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let calendar = DomainCalendar::collect(&c).await.unwrap();
+///     std::fs::write("domains.ics", calendar.to_ics()).unwrap();
+///     std::fs::write("domains.json", serde_json::to_string(&calendar).unwrap()).unwrap();
+///     for (domain, e) in &calendar.failed {
+///         eprintln!("{}: {}", domain, e);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Serialize)]
+pub struct DomainCalendar {
+    /// Renewal deadlines found
+    pub renewals: Vec<DomainRenewal>,
+    /// Domains whose information could not be retrieved, with the error
+    #[serde(skip)]
+    pub failed: Vec<(String, Box<dyn std::error::Error + Send + Sync>)>,
+}
+
+impl DomainCalendar {
+    /// Collects the expiration date and renewal mode of every domain.
+    ///
+    /// Listing the domains failing makes the whole collection fail, while a
+    /// single domain failing is reported in [`failed`](Self::failed).
+    pub async fn collect(c: &OvhClient) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let domains: Vec<String> = c.get_json("/domain").await?;
+        let res = fetch_all(&domains, c.fetch_concurrency(), |domain| {
+            let path = format!("/domain/{}", domain);
+            async move { OvhServiceInfos::get(c, &path).await }
+        })
+        .await;
+
+        let mut calendar = Self::default();
+        for (domain, r) in domains.into_iter().zip(res) {
+            match r {
+                Ok(infos) => calendar.renewals.push(DomainRenewal {
+                    renew_mode: infos.renew.as_ref().into(),
+                    expiration: infos.expiration,
+                    domain,
+                }),
+                Err(e) => calendar.failed.push((domain, e)),
+            }
+        }
+        calendar.renewals.sort_by(|a, b| {
+            a.days_left()
+                .cmp(&b.days_left())
+                .then_with(|| a.domain.cmp(&b.domain))
+        });
+        Ok(calendar)
+    }
+
+    /// Exports the renewal deadlines as an iCalendar feed, with an all-day
+    /// event on the expiration date of each domain.
+    ///
+    /// Domains whose expiration date cannot be read are left out.
+    ///
+    /// ```
+    /// use ovh::domain_calendar::{DomainCalendar, DomainRenewal, RenewMode};
+    ///
+    /// let calendar = DomainCalendar {
+    ///     renewals: vec![DomainRenewal {
+    ///         domain: "example.com".into(),
+    ///         expiration: "2030-03-01".into(),
+    ///         renew_mode: RenewMode::Manual,
+    ///     }],
+    ///     failed: Vec::new(),
+    /// };
+    ///
+    /// let ics = calendar.to_ics();
+    /// assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+    /// assert!(ics.contains("DTSTART;VALUE=DATE:20300301\r\n"));
+    /// assert!(ics.contains("SUMMARY:example.com expires (manual renewal)\r\n"));
+    /// ```
+    pub fn to_ics(&self) -> String {
+        let stamp = format!("{}T000000Z", format_day(today()).replace('-', ""));
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//rust-ovh//Domain renewals//EN".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+            "X-WR-CALNAME:Domain renewals".to_string(),
+        ];
+        for r in &self.renewals {
+            let day = match parse_day(&r.expiration) {
+                Some(day) => day,
+                None => continue,
+            };
+            let domain = escape_text(&r.domain);
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:{}@rust-ovh", domain),
+                format!("DTSTAMP:{}", stamp),
+                format!("DTSTART;VALUE=DATE:{}", format_day(day).replace('-', "")),
+                format!("DTEND;VALUE=DATE:{}", format_day(day + 1).replace('-', "")),
+                format!("SUMMARY:{} expires ({})", domain, r.renew_mode),
+                "TRANSP:TRANSPARENT".to_string(),
+                "END:VEVENT".to_string(),
+            ]);
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        let mut ics = lines.join("\r\n");
+        ics.push_str("\r\n");
+        ics
+    }
+}
+
+/// Escapes the characters with a meaning in iCalendar text values.
+fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
@@ -1,22 +1,96 @@
 //! High-level access to the email redirection API.
 
 use core::fmt;
-use std::fmt::Display;
+use std::{
+    collections::HashSet,
+    convert::TryFrom,
+    fmt::Display,
+    num::ParseIntError,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
 use crate::client::OvhClient;
+use crate::fetch::fetch_all;
+use crate::plan::{Change, ChangeAction, Plan, ResourceKind};
 use reqwest::Response;
 
 use serde::{Deserialize, Serialize};
 
+/// Maximum time waited for the redirections created by
+/// [`OvhMailRedir::create_many`] to appear before rolling them back.
+const ROLLBACK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Delay between two lookups of the redirections to roll back.
+const ROLLBACK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Identifier of an email redirection.
+///
+/// The API exchanges identifiers as numeric strings.
+///
+/// ```
+/// use ovh::email_redir::RedirId;
+///
+/// let id: RedirId = "1234567".parse().unwrap();
+/// assert_eq!(id, RedirId::from(1234567));
+/// assert_eq!(id.to_string(), "1234567");
+/// assert_eq!(serde_json::to_string(&id).unwrap(), r#""1234567""#);
+/// assert!("abc".parse::<RedirId>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RedirId(u64);
+
+impl RedirId {
+    /// Returns the numeric value of the identifier.
+    pub fn get(self) -> u64 {
+        self.0
+    }
+}
+
+impl From<u64> for RedirId {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+impl FromStr for RedirId {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+impl TryFrom<String> for RedirId {
+    type Error = ParseIntError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<RedirId> for String {
+    fn from(id: RedirId) -> Self {
+        id.to_string()
+    }
+}
+
+impl Display for RedirId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// Structure representing a single email redirection.
 #[derive(Debug, Deserialize)]
 pub struct OvhMailRedir {
     /// Unique identifier of the redirection
-    pub id: String,
-    /// Email address to redirect from
-    pub from: String,
-    /// Email address to redirect to
-    pub to: String,
+    pub id: RedirId,
+    /// Email address to redirect from, if the endpoint tells it
+    pub from: Option<String>,
+    /// Email address to redirect to, if the endpoint tells it
+    pub to: Option<String>,
 }
 
 impl OvhMailRedir {
@@ -24,12 +98,10 @@ impl OvhMailRedir {
     async fn get_redir(
         client: &OvhClient,
         domain: &str,
-        id: &str,
-    ) -> Result<OvhMailRedir, Box<dyn std::error::Error>> {
+        id: RedirId,
+    ) -> Result<OvhMailRedir, Box<dyn std::error::Error + Send + Sync>> {
         let res = client
-            .get(&format!("/email/domain/{}/redirection/{}", domain, id))
-            .await?
-            .json()
+            .get_json(&format!("/email/domain/{}/redirection/{}", domain, id))
             .await?;
         Ok(res)
     }
@@ -58,16 +130,14 @@ impl OvhMailRedir {
     pub async fn list(
         client: &OvhClient,
         domain: &str,
-    ) -> Result<Vec<OvhMailRedir>, Box<dyn std::error::Error>> {
-        let resp = client
-            .get(&format!("/email/domain/{}/redirection", domain))
+    ) -> Result<Vec<OvhMailRedir>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = client
+            .get_json::<Vec<RedirId>>(&format!("/email/domain/{}/redirection", domain))
             .await?;
-        let resp = resp.error_for_status()?;
-
-        let res = resp.json::<Vec<String>>().await?;
-        let res: Vec<_> =
-            futures::future::join_all(res.iter().map(|id| Self::get_redir(client, domain, id)))
-                .await;
+        let res = fetch_all(&res, client.fetch_concurrency(), |id| {
+            Self::get_redir(client, domain, *id)
+        })
+        .await;
 
         let res = res.into_iter().filter_map(|c| c.ok()).collect();
 
@@ -94,7 +164,7 @@ impl OvhMailRedir {
         from: &str,
         to: &str,
         local_copy: bool,
-    ) -> Result<Response, Box<dyn std::error::Error>> {
+    ) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
         let data = OvhMailRedirCreate {
             from,
             to,
@@ -104,7 +174,253 @@ impl OvhMailRedir {
             .await
     }
 
-    /// Deletes an existing redirection.
+    /// Creates several redirections concurrently.
+    ///
+    /// Every entry is created independently and reported in the returned
+    /// outcomes, in the same order as `entries`. When `rollback` is set and
+    /// at least one creation failed, the redirections that were successfully
+    /// created are deleted again so the domain is left untouched.
+    ///
+    /// Rolling back only deletes redirections created by this call: the
+    /// existing redirections of every source and destination are listed
+    /// beforehand, and entries whose existing redirections cannot be listed
+    /// are not created. As redirections are created by asynchronous tasks,
+    /// the rollback waits up to a minute for them to appear.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::email_redir::{OvhMailRedir, OvhMailRedirEntry};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let entries = vec![
+    ///         OvhMailRedirEntry::new("foo@example.com", "admin@example.com", false),
+    ///         OvhMailRedirEntry::new("bar@example.com", "admin@example.com", false),
+    ///     ];
+    ///     let outcomes = OvhMailRedir::create_many(&c, "example.com", &entries, true).await;
+    ///
+    ///     for o in outcomes {
+    ///         println!("{} -> {}: {:?}", o.entry.from, o.entry.to, o.status);
+    ///     }
+    /// }
+    /// ```
+    pub async fn create_many(
+        c: &OvhClient,
+        domain: &str,
+        entries: &[OvhMailRedirEntry],
+        rollback: bool,
+    ) -> Vec<OvhMailRedirOutcome> {
+        let mut pairs: Vec<(&str, &str)> = Vec::new();
+        for e in entries {
+            if !pairs.contains(&(e.from.as_str(), e.to.as_str())) {
+                pairs.push((&e.from, &e.to));
+            }
+        }
+        let existing = if rollback {
+            futures::future::join_all(
+                pairs
+                    .iter()
+                    .map(|&(from, to)| Self::list_ids_matching(c, domain, from, to)),
+            )
+            .await
+        } else {
+            Vec::new()
+        };
+        let existing_for = |e: &OvhMailRedirEntry| {
+            let i = pairs.iter().position(|&(f, t)| f == e.from && t == e.to)?;
+            existing.get(i)
+        };
+
+        let res = futures::future::join_all(entries.iter().map(|e| async move {
+            if let Some(Err(err)) = existing_for(e) {
+                return Err(format!("could not list existing redirections: {}", err).into());
+            }
+            Self::create(c, domain, &e.from, &e.to, e.local_copy)
+                .await?
+                .error_for_status()?;
+            Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+        }))
+        .await;
+
+        let mut outcomes: Vec<_> = entries
+            .iter()
+            .zip(res)
+            .map(|(entry, r)| OvhMailRedirOutcome {
+                entry: entry.clone(),
+                status: match r {
+                    Ok(()) => OvhMailRedirStatus::Created,
+                    Err(e) => OvhMailRedirStatus::Failed(e.to_string()),
+                },
+            })
+            .collect();
+
+        let failed = outcomes
+            .iter()
+            .any(|o| matches!(o.status, OvhMailRedirStatus::Failed(_)));
+        if !rollback || !failed {
+            return outcomes;
+        }
+
+        let rollbacks = pairs.iter().zip(&existing).map(|(&(from, to), existing)| {
+            let created: Vec<usize> = outcomes
+                .iter()
+                .enumerate()
+                .filter(|(_, o)| {
+                    o.status == OvhMailRedirStatus::Created
+                        && o.entry.from == from
+                        && o.entry.to == to
+                })
+                .map(|(i, _)| i)
+                .collect();
+            async move {
+                let res = match existing {
+                    Ok(existing) if !created.is_empty() => {
+                        Self::delete_created(c, domain, from, to, existing, created.len()).await
+                    }
+                    _ => Vec::new(),
+                };
+                created.into_iter().zip(res).collect::<Vec<_>>()
+            }
+        });
+        let res = futures::future::join_all(rollbacks).await;
+
+        for (i, r) in res.into_iter().flatten() {
+            outcomes[i].status = match r {
+                Ok(()) => OvhMailRedirStatus::RolledBack,
+                Err(e) => OvhMailRedirStatus::RollbackFailed(e),
+            };
+        }
+
+        outcomes
+    }
+
+    /// Computes the redirections of `entries` that do not exist yet on the
+    /// domain, without creating them.
+    ///
+    /// Addresses are compared without regard to case. Creating the entries
+    /// of the plan with [`create_many`](Self::create_many) imports them
+    /// without duplicates.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::email_redir::{OvhMailRedir, OvhMailRedirEntry};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let entries = vec![OvhMailRedirEntry::new("foo@example.com", "admin@example.com", false)];
+    ///     let plan = OvhMailRedir::plan_import(&c, "example.com", &entries).await.unwrap();
+    ///     print!("{}", plan);
+    /// }
+    /// ```
+    pub async fn plan_import(
+        c: &OvhClient,
+        domain: &str,
+        entries: &[OvhMailRedirEntry],
+    ) -> Result<Plan, Box<dyn std::error::Error + Send + Sync>> {
+        let mut existing: HashSet<_> = Self::list(c, domain)
+            .await?
+            .iter()
+            .filter_map(OvhMailRedir::addresses)
+            .collect();
+
+        let plan = entries
+            .iter()
+            .filter(|e| existing.insert((e.from.to_lowercase(), e.to.to_lowercase())))
+            .map(|e| Change {
+                kind: ResourceKind::EmailRedirection,
+                resource: format!("{}/{} -> {}", domain, e.from, e.to),
+                action: ChangeAction::Create,
+                before: None,
+                after: Some(serde_json::json!({
+                    "from": e.from,
+                    "to": e.to,
+                    "local_copy": e.local_copy,
+                })),
+            })
+            .collect();
+        Ok(plan)
+    }
+
+    /// Returns the source and destination addresses in lowercase, or
+    /// `None` if one of them is unknown.
+    fn addresses(&self) -> Option<(String, String)> {
+        Some((
+            self.from.as_deref()?.to_lowercase(),
+            self.to.as_deref()?.to_lowercase(),
+        ))
+    }
+
+    /// Lists the identifiers of the redirections from an address to
+    /// another.
+    async fn list_ids_matching(
+        c: &OvhClient,
+        domain: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<HashSet<RedirId>, Box<dyn std::error::Error + Send + Sync>> {
+        let resp = c
+            .get_with_params(
+                &format!("/email/domain/{}/redirection", domain),
+                &[("from", from), ("to", to)],
+            )
+            .await?;
+        let res = c.read_json(resp).await?;
+        Ok(res)
+    }
+
+    /// Deletes `count` redirections from an address to another created
+    /// since `existing` was listed, reporting the outcome of each deletion.
+    async fn delete_created(
+        c: &OvhClient,
+        domain: &str,
+        from: &str,
+        to: &str,
+        existing: &HashSet<RedirId>,
+        count: usize,
+    ) -> Vec<Result<(), String>> {
+        // The creation call only returns a task, so the identifiers of the
+        // new redirections have to be looked up once it has run.
+        let deadline = Instant::now() + ROLLBACK_TIMEOUT;
+        let created = loop {
+            let ids = match Self::list_ids_matching(c, domain, from, to).await {
+                Ok(ids) => ids,
+                Err(e) => return vec![Err(e.to_string()); count],
+            };
+            let mut created: Vec<RedirId> = ids.difference(existing).copied().collect();
+            created.sort();
+            if created.len() >= count || Instant::now() + ROLLBACK_POLL_INTERVAL > deadline {
+                break created;
+            }
+            c.sleep(ROLLBACK_POLL_INTERVAL).await;
+        };
+
+        let mut res = Vec::new();
+        for i in 0..count {
+            res.push(match created.get(i) {
+                Some(&id) => async {
+                    Self::delete(c, domain, id).await?.error_for_status()?;
+                    Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+                }
+                .await
+                .map_err(|e| e.to_string()),
+                None => Err(format!(
+                    "redirection not found after {}s",
+                    ROLLBACK_TIMEOUT.as_secs()
+                )),
+            });
+        }
+        res
+    }
+
+    /// Removes the redirections having the same source and destination as
+    /// another one, keeping a single redirection of each pair.
+    ///
+    /// Addresses are compared without regard to case, and redirections
+    /// whose source or destination is unknown are left alone. Returns the
+    /// redirections deleted, or the ones that would be deleted when
+    /// `dry_run` is set, in which case nothing is changed.
     ///
     /// ```no_run
     /// use ovh::client::OvhClient;
@@ -113,7 +429,42 @@ impl OvhMailRedir {
     /// #[tokio::main]
     /// async fn main() {
     ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
-    ///     OvhMailRedir::delete(&c, "example.com", "1234567")
+    ///     for r in OvhMailRedir::dedupe(&c, "example.com", true).await.unwrap() {
+    ///         println!("would delete {}", r);
+    ///     }
+    /// }
+    /// ```
+    pub async fn dedupe(
+        c: &OvhClient,
+        domain: &str,
+        dry_run: bool,
+    ) -> Result<Vec<OvhMailRedir>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut seen = HashSet::new();
+        let duplicates: Vec<_> = Self::list(c, domain)
+            .await?
+            .into_iter()
+            .filter(|r| r.addresses().is_some_and(|a| !seen.insert(a)))
+            .collect();
+
+        if !dry_run {
+            for r in &duplicates {
+                Self::delete(c, domain, r.id).await?.error_for_status()?;
+            }
+        }
+
+        Ok(duplicates)
+    }
+
+    /// Deletes an existing redirection.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::email_redir::{OvhMailRedir, RedirId};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     OvhMailRedir::delete(&c, "example.com", RedirId::from(1234567))
     ///         .await
     ///         .unwrap();
     /// }
@@ -121,11 +472,231 @@ impl OvhMailRedir {
     pub async fn delete(
         c: &OvhClient,
         domain: &str,
-        id: &str,
-    ) -> Result<Response, Box<dyn std::error::Error>> {
+        id: RedirId,
+    ) -> Result<Response, Box<dyn std::error::Error + Send + Sync>> {
         c.delete(&format!("/email/domain/{}/redirection/{}", domain, id))
             .await
     }
+
+    /// Gathers what the API knows about the delivery of a redirection, to
+    /// investigate one that stopped forwarding.
+    ///
+    /// The diagnosis holds the redirection tasks still pending for its
+    /// source address, the MX records of the domain and, when the source
+    /// address is also a mailbox of the domain, the last diagnosis of that
+    /// mailbox. Nothing is modified.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::email_redir::{OvhMailRedir, RedirId};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let diagnosis = OvhMailRedir::diagnose(&c, "example.com", RedirId::from(1234567))
+    ///         .await
+    ///         .unwrap();
+    ///     for problem in diagnosis.problems() {
+    ///         println!("{}", problem);
+    ///     }
+    /// }
+    /// ```
+    pub async fn diagnose(
+        c: &OvhClient,
+        domain: &str,
+        id: RedirId,
+    ) -> Result<OvhMailRedirDiagnosis, Box<dyn std::error::Error + Send + Sync>> {
+        let redirection = Self::get_redir(c, domain, id).await?;
+        let from = redirection
+            .from
+            .as_deref()
+            .ok_or_else(|| format!("redirection {} has no source address", id))?;
+        let (local, from_domain) = from.rsplit_once('@').unwrap_or((from, domain));
+
+        let path = format!("/email/domain/{}/task/redirection", domain);
+        let resp = c.get_with_params(&path, &[("account", local)]).await?;
+        let ids: Vec<u64> = c.read_json(resp).await?;
+        let tasks = fetch_all(&ids, c.fetch_concurrency(), |id| {
+            let task = format!("{}/{}", path, id);
+            async move { c.get_json::<OvhMailTask>(&task).await }
+        })
+        .await;
+        let pending_tasks = tasks.into_iter().collect::<Result<_, _>>()?;
+
+        let mx_records = c
+            .get_json(&format!("/email/domain/{}/dnsMXRecord", domain))
+            .await?;
+
+        let account = if from_domain.eq_ignore_ascii_case(domain) {
+            c.get_opt(&format!(
+                "/email/domain/{}/account/{}/diagnostic",
+                domain, local
+            ))
+            .await?
+        } else {
+            None
+        };
+
+        Ok(OvhMailRedirDiagnosis {
+            redirection,
+            pending_tasks,
+            mx_records,
+            account,
+        })
+    }
+}
+
+/// Structure representing a task of an email domain.
+#[derive(Debug, Deserialize)]
+pub struct OvhMailTask {
+    /// Unique identifier of the task
+    pub id: u64,
+    /// What the task does, such as `add` or `delete`
+    pub action: String,
+    /// Creation date of the task
+    pub date: String,
+    /// Account the task applies to
+    pub account: Option<String>,
+}
+
+/// Structure representing the diagnosis of a mailbox, computed by the API.
+#[derive(Debug, Deserialize)]
+pub struct OvhMailAccountDiagnosis {
+    /// Errors found
+    #[serde(default)]
+    pub error: Vec<String>,
+    /// Whether the mailbox is locked
+    #[serde(rename(deserialize = "isLocked"), default)]
+    pub is_locked: bool,
+    /// Whether the MX records of the domain point to the mail servers, or
+    /// `None` if the API did not check them
+    #[serde(rename(deserialize = "isMxValid"), default)]
+    pub is_mx_valid: Option<bool>,
+    /// Whether the mailbox was flagged for sending spam
+    #[serde(rename(deserialize = "isSpammer"), default)]
+    pub is_spammer: bool,
+    /// Whether the mailbox is suspended
+    #[serde(rename(deserialize = "isSuspended"), default)]
+    pub is_suspended: bool,
+    /// Date the mailbox last received a message
+    #[serde(rename(deserialize = "lastReceivedMessageDate"))]
+    pub last_received_message_date: Option<String>,
+}
+
+/// Delivery diagnosis of a redirection, returned by
+/// [`OvhMailRedir::diagnose`].
+#[derive(Debug)]
+pub struct OvhMailRedirDiagnosis {
+    /// The redirection
+    pub redirection: OvhMailRedir,
+    /// Redirection tasks still pending for the source address
+    pub pending_tasks: Vec<OvhMailTask>,
+    /// MX records of the domain
+    pub mx_records: Vec<String>,
+    /// Last diagnosis of the mailbox of the source address, if it is a
+    /// mailbox of the domain that was diagnosed
+    pub account: Option<OvhMailAccountDiagnosis>,
+}
+
+impl OvhMailRedirDiagnosis {
+    /// Describes the problems found, if any.
+    ///
+    /// ```
+    /// use ovh::email_redir::{OvhMailRedir, OvhMailRedirDiagnosis, RedirId};
+    ///
+    /// let diagnosis = OvhMailRedirDiagnosis {
+    ///     redirection: OvhMailRedir {
+    ///         id: RedirId::from(1234567),
+    ///         from: Some("foo@example.com".into()),
+    ///         to: Some("admin@example.com".into()),
+    ///     },
+    ///     pending_tasks: Vec::new(),
+    ///     mx_records: Vec::new(),
+    ///     account: None,
+    /// };
+    /// assert_eq!(diagnosis.problems(), ["the domain has no MX record"]);
+    ///
+    /// // MX records not checked by the API are not reported.
+    /// let diagnosis = OvhMailRedirDiagnosis {
+    ///     mx_records: vec!["10 mx1.mail.ovh.net.".into()],
+    ///     account: Some(serde_json::from_str(r#"{"isSuspended": true}"#).unwrap()),
+    ///     ..diagnosis
+    /// };
+    /// assert_eq!(diagnosis.problems(), ["the source mailbox is suspended"]);
+    /// ```
+    pub fn problems(&self) -> Vec<String> {
+        let mut res = Vec::new();
+        for t in &self.pending_tasks {
+            res.push(format!(
+                "task {} ({}) pending since {}",
+                t.id, t.action, t.date
+            ));
+        }
+        if self.mx_records.is_empty() {
+            res.push("the domain has no MX record".to_string());
+        }
+        if let Some(a) = &self.account {
+            if a.is_mx_valid == Some(false) {
+                res.push("the MX records do not point to the mail servers".to_string());
+            }
+            if a.is_suspended {
+                res.push("the source mailbox is suspended".to_string());
+            }
+            if a.is_locked {
+                res.push("the source mailbox is locked".to_string());
+            }
+            if a.is_spammer {
+                res.push("the source mailbox is flagged for spam".to_string());
+            }
+            res.extend(a.error.iter().cloned());
+        }
+        res
+    }
+}
+
+/// Parameters of a redirection to create with [`OvhMailRedir::create_many`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OvhMailRedirEntry {
+    /// Email address to redirect from
+    pub from: String,
+    /// Email address to redirect to
+    pub to: String,
+    /// Keep a local copy of redirected messages
+    pub local_copy: bool,
+}
+
+impl OvhMailRedirEntry {
+    /// Creates a new entry.
+    pub fn new(from: &str, to: &str, local_copy: bool) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+            local_copy,
+        }
+    }
+}
+
+/// Result of the creation of a single entry in
+/// [`OvhMailRedir::create_many`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OvhMailRedirOutcome {
+    /// Entry this outcome refers to
+    pub entry: OvhMailRedirEntry,
+    /// What happened to the entry
+    pub status: OvhMailRedirStatus,
+}
+
+/// Status of an entry in [`OvhMailRedir::create_many`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OvhMailRedirStatus {
+    /// The redirection was created
+    Created,
+    /// The redirection could not be created
+    Failed(String),
+    /// The redirection was created, then deleted because another entry failed
+    RolledBack,
+    /// The redirection was created but could not be deleted during rollback
+    RollbackFailed(String),
 }
 
 #[derive(Debug, Serialize)]
@@ -139,6 +710,8 @@ struct OvhMailRedirCreate<'a> {
 
 impl Display for OvhMailRedir {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}: {} -> {}", self.id, self.from, self.to)
+        let from = self.from.as_deref().unwrap_or("?");
+        let to = self.to.as_deref().unwrap_or("?");
+        write!(f, "{}: {} -> {}", self.id, from, to)
     }
 }
@@ -0,0 +1,260 @@
+//! Endpoints of the OVH API.
+
+use std::{convert::Infallible, convert::TryFrom, fmt, str::FromStr};
+
+use reqwest::Url;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::OvhError;
+
+static ENDPOINTS: phf::Map<&'static str, Endpoint> = phf::phf_map! {
+    "ovh-eu" => Endpoint::OvhEu,
+    "ovh-us" => Endpoint::OvhUs,
+    "ovh-ca" => Endpoint::OvhCa,
+    "kimsufi-eu" => Endpoint::KimsufiEu,
+    "kimsufi-ca" => Endpoint::KimsufiCa,
+    "soyoustart-eu" => Endpoint::SoyoustartEu,
+    "soyoustart-ca" => Endpoint::SoyoustartCa,
+};
+
+/// API endpoint targeted by a client.
+///
+/// Endpoints are parsed from, displayed and serialized as their name, such
+/// as `ovh-eu`, or as their URL for custom endpoints.
+///
+/// ```
+/// use ovh::endpoint::Endpoint;
+///
+/// let e: Endpoint = "ovh-ca".parse().unwrap();
+/// assert_eq!(e, Endpoint::OvhCa);
+/// assert_eq!(e.url(), "https://ca.api.ovh.com");
+/// assert_eq!(e.to_string(), "ovh-ca");
+///
+/// let e: Endpoint = "http://localhost:8080/".parse().unwrap();
+/// assert_eq!(e.url(), "http://localhost:8080");
+///
+/// assert!("ovh-eur".parse::<Endpoint>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// OVHcloud Europe
+    OvhEu,
+    /// OVHcloud US
+    OvhUs,
+    /// OVHcloud North America
+    OvhCa,
+    /// Kimsufi Europe
+    KimsufiEu,
+    /// Kimsufi North America
+    KimsufiCa,
+    /// So you Start Europe
+    SoyoustartEu,
+    /// So you Start North America
+    SoyoustartCa,
+    /// Any other base URL, without the API version, such as a local mock
+    /// server or a corporate proxy
+    Custom(Url),
+}
+
+/// Region of the API an endpoint belongs to.
+///
+/// Some routes and fields of the API differ between the European, US and
+/// Canadian regions, whatever the brand of the endpoint. The structures of
+/// this crate accept the responses of every region, the fields missing
+/// outside Europe taking a default value:
+///
+/// ```
+/// use ovh::client::OvhCredential;
+/// use ovh::dedicated_server::OvhDedicatedServerTask;
+/// use ovh::dns_record::{DnsRecordType, OvhDnsRecord};
+/// use ovh::dns_zone::{OvhDnsZone, OvhDnsZoneSoa};
+/// use ovh::email_redir::OvhMailRedir;
+/// use ovh::hosting_web::OvhHostingWebSsl;
+/// use ovh::ip::OvhIp;
+/// use ovh::service::{OvhService, OvhServiceInfos};
+///
+/// // GET /domain/zone/example.ca on ovh-ca
+/// let zone: OvhDnsZone = serde_json::from_str(
+///     r#"{"name": "example.ca", "lastUpdate": "2024-03-04T09:12:44-05:00"}"#,
+/// )
+/// .unwrap();
+/// assert!(!zone.has_dns_anycast);
+/// assert!(zone.name_servers.is_empty());
+///
+/// // GET /domain/zone/example.ca/soa on ovh-ca
+/// let soa: OvhDnsZoneSoa =
+///     serde_json::from_str(r#"{"server": "dns10.ovh.ca.", "serial": 2024030401}"#).unwrap();
+/// assert_eq!(soa.ttl, 0);
+///
+/// // GET /domain/zone/example.ca/record/5102441 on ovh-ca
+/// let record: OvhDnsRecord = serde_json::from_str(
+///     r#"{"id": 5102441, "fieldType": "MX", "target": "1 mx1.mail.ovh.ca."}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(record.field_type, DnsRecordType::Mx);
+/// assert_eq!((record.zone.as_str(), record.sub_domain.as_str(), record.ttl), ("", "", 0));
+///
+/// // GET /email/domain/example.ca/redirection/1234567 on ovh-ca
+/// let redir: OvhMailRedir =
+///     serde_json::from_str(r#"{"id": "1234567", "from": "sales@example.ca"}"#).unwrap();
+/// assert_eq!(redir.to, None);
+///
+/// // GET /hosting/web/example.ca/ssl on ovh-ca
+/// let ssl: OvhHostingWebSsl =
+///     serde_json::from_str(r#"{"status": "created", "isReportable": false}"#).unwrap();
+/// assert_eq!(ssl.provider, "");
+///
+/// // GET /domain/example.ca/serviceInfos on ovh-ca
+/// let infos: OvhServiceInfos = serde_json::from_str(
+///     r#"{"domain": "example.ca", "serviceId": 31415926, "creation": "2021-06-01"}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(infos.contact_tech, "");
+///
+/// // GET /services/31415927 on ovh-us
+/// let service: OvhService = serde_json::from_str(
+///     r#"{"serviceId": 31415927, "route": {"path": "/dedicated/server/{serviceName}"}}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(service.resource.name, "");
+///
+/// // GET /ip/192.0.2.0%2F29 on ovh-us
+/// let ip: OvhIp = serde_json::from_str(r#"{"ip": "192.0.2.0/29", "canBeTerminated": true}"#)
+///     .unwrap();
+/// assert_eq!(ip.ip_type, "");
+///
+/// // GET /dedicated/server/ns1234.ip-192-0-2.us/task/8472 on ovh-us
+/// let task: OvhDedicatedServerTask =
+///     serde_json::from_str(r#"{"taskId": 8472, "status": "done"}"#).unwrap();
+/// assert_eq!(task.function, "");
+///
+/// // GET /me/api/credential/42 on ovh-us
+/// let cred: OvhCredential = serde_json::from_str(
+///     r#"{"credentialId": 42, "applicationId": 7, "status": "validated"}"#,
+/// )
+/// .unwrap();
+/// assert!(cred.rules.is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Region {
+    /// Europe
+    Eu,
+    /// United States
+    Us,
+    /// Canada and the rest of North America
+    Ca,
+}
+
+impl Endpoint {
+    /// Returns the region of the endpoint, or `None` for a custom one.
+    ///
+    /// ```
+    /// use ovh::endpoint::{Endpoint, Region};
+    ///
+    /// assert_eq!(Endpoint::KimsufiCa.region(), Some(Region::Ca));
+    /// assert_eq!("http://localhost:8080".parse::<Endpoint>().unwrap().region(), None);
+    /// ```
+    pub fn region(&self) -> Option<Region> {
+        match self {
+            Endpoint::OvhEu | Endpoint::KimsufiEu | Endpoint::SoyoustartEu => Some(Region::Eu),
+            Endpoint::OvhUs => Some(Region::Us),
+            Endpoint::OvhCa | Endpoint::KimsufiCa | Endpoint::SoyoustartCa => Some(Region::Ca),
+            Endpoint::Custom(_) => None,
+        }
+    }
+
+    /// Returns the name of the endpoint, or its URL for a custom endpoint.
+    pub fn name(&self) -> &str {
+        match self {
+            Endpoint::OvhEu => "ovh-eu",
+            Endpoint::OvhUs => "ovh-us",
+            Endpoint::OvhCa => "ovh-ca",
+            Endpoint::KimsufiEu => "kimsufi-eu",
+            Endpoint::KimsufiCa => "kimsufi-ca",
+            Endpoint::SoyoustartEu => "soyoustart-eu",
+            Endpoint::SoyoustartCa => "soyoustart-ca",
+            Endpoint::Custom(_) => self.url(),
+        }
+    }
+
+    /// Returns the base URL of the endpoint, without the API version nor a
+    /// trailing slash.
+    pub fn url(&self) -> &str {
+        match self {
+            Endpoint::OvhEu => "https://eu.api.ovh.com",
+            Endpoint::OvhUs => "https://api.us.ovhcloud.com",
+            Endpoint::OvhCa => "https://ca.api.ovh.com",
+            Endpoint::KimsufiEu => "https://eu.api.kimsufi.com",
+            Endpoint::KimsufiCa => "https://ca.api.kimsufi.com",
+            Endpoint::SoyoustartEu => "https://eu.api.soyoustart.com",
+            Endpoint::SoyoustartCa => "https://ca.api.soyoustart.com",
+            Endpoint::Custom(url) => url.as_str().trim_end_matches('/'),
+        }
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = OvhError;
+
+    /// Parses an endpoint name, or an `http://` or `https://` URL.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(e) = ENDPOINTS.get(s) {
+            return Ok(e.clone());
+        }
+        if s.starts_with("https://") || s.starts_with("http://") {
+            if let Ok(url) = Url::parse(s) {
+                return Ok(Endpoint::Custom(url));
+            }
+        }
+        Err(OvhError::UnknownEndpoint(s.into()))
+    }
+}
+
+impl TryFrom<&str> for Endpoint {
+    type Error = OvhError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<&String> for Endpoint {
+    type Error = OvhError;
+
+    fn try_from(s: &String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for Endpoint {
+    type Error = OvhError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Infallible> for OvhError {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl Serialize for Endpoint {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for Endpoint {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,188 @@
+//! Errors returned by the client.
+
+use std::{fmt, time::Duration};
+
+use reqwest::StatusCode;
+
+/// Error returned by the client when a request could not be performed.
+#[derive(Debug)]
+pub enum OvhError {
+    /// The API answered with an error status.
+    Api {
+        /// HTTP status of the response
+        status: StatusCode,
+        /// Machine-readable error code (`INVALID_SIGNATURE`...), if any
+        error_code: Option<String>,
+        /// Human-readable error message
+        message: String,
+        /// Identifier of the query, to give to the OVH support
+        query_id: Option<String>,
+    },
+    /// Every attempt of an idempotent request failed with a transient
+    /// error.
+    RetriesExhausted {
+        /// The attempts made, in order
+        attempts: Vec<OvhAttempt>,
+    },
+    /// The request did not complete before its deadline.
+    Timeout(Duration),
+    /// The endpoint given to build a client is neither a known endpoint
+    /// name nor an HTTP URL.
+    UnknownEndpoint(String),
+    /// The key with the given name is empty or holds characters other than
+    /// visible ASCII ones.
+    ///
+    /// Keys are checked when the client is built, so that hostile values
+    /// cannot inject headers:
+    ///
+    /// ```
+    /// use ovh::client::OvhClient;
+    /// use ovh::error::OvhError;
+    ///
+    /// for key in ["ck\r\nX-Injected: 1", "clé", "ck\0", "c k"] {
+    ///     let err = OvhClient::try_new("ovh-eu", "app_key", "app_secret", key).unwrap_err();
+    ///     assert!(matches!(err, OvhError::InvalidKeyFormat("consumer_key")));
+    ///     let err = OvhClient::try_new("ovh-eu", key, "app_secret", "ck").unwrap_err();
+    ///     assert!(matches!(err, OvhError::InvalidKeyFormat("application_key")));
+    /// }
+    /// ```
+    InvalidKeyFormat(&'static str),
+    /// The value of the header with the given name holds characters not
+    /// allowed in headers.
+    InvalidHeaderValue(&'static str),
+    /// The local clock is set before the Unix epoch, so requests cannot be
+    /// timestamped.
+    ClockBeforeEpoch,
+    /// The body of a response exceeds the maximum size set with
+    /// [`OvhClient::with_max_response_size`](crate::client::OvhClient::with_max_response_size),
+    /// in bytes.
+    ResponseTooLarge {
+        /// The maximum size
+        limit: usize,
+    },
+    /// A mutating request was denied by a
+    /// [`Policy`](crate::policy::Policy) of the client, for the given
+    /// reason.
+    PolicyDenied(String),
+    /// The HTTP client could not be built.
+    Http(reqwest::Error),
+    /// A server error happened while incidents were ongoing on the
+    /// product, see [`annotate_error`](crate::status::annotate_error).
+    PossibleIncident {
+        /// The original error
+        error: Box<OvhError>,
+        /// Titles of the ongoing incidents
+        incidents: Vec<String>,
+    },
+}
+
+/// A single failed attempt of a request.
+#[derive(Debug, Clone)]
+pub struct OvhAttempt {
+    /// Why the attempt failed
+    pub outcome: OvhAttemptOutcome,
+    /// Time spent on the attempt
+    pub elapsed: Duration,
+}
+
+/// Reason of the failure of an attempt.
+#[derive(Debug, Clone)]
+pub enum OvhAttemptOutcome {
+    /// The server answered with a status worth retrying (429 or 5xx)
+    Status(StatusCode),
+    /// The request did not reach the server or timed out
+    Network(String),
+}
+
+impl fmt::Display for OvhAttempt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.outcome {
+            OvhAttemptOutcome::Status(s) => write!(f, "{}", s)?,
+            OvhAttemptOutcome::Network(e) => write!(f, "network error: {}", e)?,
+        }
+        write!(f, " after {}ms", self.elapsed.as_millis())
+    }
+}
+
+/// ```
+/// use std::time::Duration;
+/// use ovh::error::{OvhAttempt, OvhAttemptOutcome, OvhError};
+///
+/// let err = OvhError::RetriesExhausted {
+///     attempts: vec![
+///         OvhAttempt {
+///             outcome: OvhAttemptOutcome::Status(reqwest::StatusCode::TOO_MANY_REQUESTS),
+///             elapsed: Duration::from_millis(120),
+///         },
+///         OvhAttempt {
+///             outcome: OvhAttemptOutcome::Network("connection reset".into()),
+///             elapsed: Duration::from_millis(30),
+///         },
+///     ],
+/// };
+/// assert_eq!(
+///     err.to_string(),
+///     "request failed after 2 attempts: \
+///      [429 Too Many Requests after 120ms, network error: connection reset after 30ms]"
+/// );
+/// ```
+impl fmt::Display for OvhError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OvhError::Api {
+                status,
+                error_code,
+                message,
+                query_id,
+            } => {
+                write!(f, "API error {}", status)?;
+                if let Some(code) = error_code {
+                    write!(f, " {}", code)?;
+                }
+                write!(f, ": {}", message)?;
+                if let Some(id) = query_id {
+                    write!(f, " (query id {})", id)?;
+                }
+                Ok(())
+            }
+            OvhError::RetriesExhausted { attempts } => {
+                write!(f, "request failed after {} attempts: [", attempts.len())?;
+                for (i, a) in attempts.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", a)?;
+                }
+                write!(f, "]")
+            }
+            OvhError::Timeout(d) => write!(f, "request timed out after {}ms", d.as_millis()),
+            OvhError::UnknownEndpoint(name) => write!(f, "unknown endpoint `{}`", name),
+            OvhError::InvalidKeyFormat(name) => write!(f, "invalid format of `{}`", name),
+            OvhError::InvalidHeaderValue(name) => {
+                write!(f, "invalid characters in header `{}`", name)
+            }
+            OvhError::ClockBeforeEpoch => write!(f, "system clock is set before the Unix epoch"),
+            OvhError::ResponseTooLarge { limit } => {
+                write!(f, "response body larger than {} bytes", limit)
+            }
+            OvhError::PolicyDenied(reason) => write!(f, "request denied by policy: {}", reason),
+            OvhError::Http(e) => write!(f, "failed to build HTTP client: {}", e),
+            OvhError::PossibleIncident { error, incidents } => write!(
+                f,
+                "{} (possible ongoing incident: {})",
+                error,
+                incidents.join("; ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OvhError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OvhError::Http(e) => Some(e),
+            OvhError::PossibleIncident { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
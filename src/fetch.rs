@@ -0,0 +1,48 @@
+//! Bounded-concurrency fetching of many resources.
+//!
+//! Listing routes of the API only return identifiers, so listing the
+//! details of a collection takes one extra request per item. Firing all of
+//! them at once trips the rate limits of the API on big collections, such
+//! as zones holding thousands of records: [`fetch_all`] keeps a bounded
+//! number of them in flight instead.
+
+use std::future::Future;
+
+use futures::StreamExt;
+
+/// Number of requests kept in flight by default by the listings of the
+/// high-level modules.
+pub const DEFAULT_CONCURRENCY: usize = 8;
+
+/// Runs `f` on every item, with at most `concurrency` futures running at
+/// the same time, and returns their outputs in the order of the items.
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::fetch::fetch_all;
+/// use ovh::ip::OvhIp;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let blocks = OvhIp::list_blocks(&c).await.unwrap();
+///     let ips = fetch_all(&blocks, 4, |b| OvhIp::get(&c, b)).await;
+/// }
+/// ```
+pub async fn fetch_all<I, F, Fut>(items: I, concurrency: usize, mut f: F) -> Vec<Fut::Output>
+where
+    I: IntoIterator,
+    F: FnMut(I::Item) -> Fut,
+    Fut: Future,
+{
+    let mut res: Vec<_> = futures::stream::iter(items.into_iter().enumerate())
+        .map(|(i, item)| {
+            let fut = f(item);
+            async move { (i, fut.await) }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+    res.sort_by_key(|(i, _)| *i);
+    res.into_iter().map(|(_, r)| r).collect()
+}
@@ -0,0 +1,74 @@
+//! High-level access to the web hosting API.
+
+use crate::client::OvhClient;
+
+use serde::{Deserialize, Serialize};
+
+/// Structure representing the certificate of a web hosting.
+#[derive(Debug, Deserialize)]
+pub struct OvhHostingWebSsl {
+    /// Current status of the certificate (`creating`, `created`...)
+    pub status: String,
+    /// Provider of the certificate (`LETSENCRYPT`, `COMODO`...), empty if
+    /// the endpoint does not tell it
+    #[serde(default)]
+    pub provider: String,
+    /// Kind of certificate (`CUSTOM`, `DV`, `EV`), empty if the endpoint
+    /// does not tell it
+    #[serde(rename(deserialize = "type"), default)]
+    pub kind: String,
+    /// Expiration date of the certificate, when reported
+    #[serde(rename(deserialize = "expirationDate"), default)]
+    pub expiration_date: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhHostingWebSslCreate<'a> {
+    certificate: &'a str,
+    key: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chain: Option<&'a str>,
+}
+
+impl OvhHostingWebSsl {
+    /// Retrieves the certificate of a web hosting, if any.
+    pub async fn get(
+        c: &OvhClient,
+        service: &str,
+    ) -> Result<Option<OvhHostingWebSsl>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_opt(&format!("/hosting/web/{}/ssl", service)).await?;
+        Ok(res)
+    }
+
+    /// Installs a custom certificate, in PEM format, on a web hosting.
+    ///
+    /// The web hosting must not already have a certificate.
+    pub async fn create(
+        c: &OvhClient,
+        service: &str,
+        certificate: &str,
+        key: &str,
+        chain: Option<&str>,
+    ) -> Result<OvhHostingWebSsl, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhHostingWebSslCreate {
+            certificate,
+            key,
+            chain,
+        };
+        let res = c
+            .post_json(&format!("/hosting/web/{}/ssl", service), &data)
+            .await?;
+        Ok(res)
+    }
+
+    /// Removes the certificate of a web hosting.
+    pub async fn delete(
+        c: &OvhClient,
+        service: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.delete(&format!("/hosting/web/{}/ssl", service))
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
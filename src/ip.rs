@@ -0,0 +1,259 @@
+//! High-level access to the IP API.
+
+use std::net::IpAddr;
+
+use crate::client::OvhClient;
+use crate::dedicated_server::OvhDedicatedServer;
+use crate::dns_record::{search_records, DnsRecordType, OvhDnsRecord};
+use crate::fetch::fetch_all;
+use crate::vps::OvhVps;
+
+use serde::Deserialize;
+
+/// Structure representing an IP block of the account.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhIp {
+    /// IP block, in CIDR notation
+    pub ip: String,
+    /// Kind of block (`dedicated`, `vps`, `failover`, `cloud`...), empty
+    /// if the endpoint does not tell it
+    #[serde(rename(deserialize = "type"), default)]
+    pub ip_type: String,
+    /// Service the block is routed to, if any
+    #[serde(rename(deserialize = "routedTo"))]
+    pub routed_to: Option<OvhIpRoutedTo>,
+    /// Description of the block
+    pub description: Option<String>,
+}
+
+/// Service an IP block is routed to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhIpRoutedTo {
+    /// Name of the service, such as the name of a dedicated server or VPS
+    #[serde(rename(deserialize = "serviceName"))]
+    pub service_name: Option<String>,
+}
+
+/// Usage of an IP block, as found by [`OvhIp::usage_report`].
+#[derive(Debug, Clone)]
+pub struct OvhIpUsage {
+    /// The IP block
+    pub ip: OvhIp,
+    /// Dedicated servers and VPS having an address of the block
+    pub servers: Vec<String>,
+    /// DNS records of the account targeting an address of the block
+    pub records: Vec<OvhDnsRecord>,
+}
+
+impl OvhIpUsage {
+    /// Returns the name of the service the block is routed to, if any,
+    /// or else of the first dedicated server or VPS having an address of
+    /// the block.
+    pub fn service(&self) -> Option<&str> {
+        let routed = self
+            .ip
+            .routed_to
+            .as_ref()
+            .and_then(|r| r.service_name.as_deref());
+        routed.or_else(|| self.servers.first().map(String::as_str))
+    }
+}
+
+/// Cross-reference of the IP blocks of the account with their services and
+/// DNS records, built by [`OvhIp::usage_report`].
+#[derive(Debug, Default)]
+pub struct OvhIpUsageReport {
+    /// Usage of every IP block of the account
+    pub ips: Vec<OvhIpUsage>,
+    /// A and AAAA records of the account targeting an address outside of
+    /// every block of the account, usually a released one
+    pub unmatched: Vec<OvhDnsRecord>,
+    /// Zones, IP blocks, servers or VPS that could not be inspected, with
+    /// the error message
+    pub failed: Vec<(String, String)>,
+}
+
+impl OvhIpUsageReport {
+    /// Returns the DNS records targeting an IP block of the account that
+    /// is not routed to any service.
+    ///
+    /// Such records usually belong to a decommissioned server and should
+    /// be removed before the block is reused. Records targeting an address
+    /// the account no longer owns are listed in
+    /// [`unmatched`](Self::unmatched).
+    pub fn dangling(&self) -> impl Iterator<Item = &OvhDnsRecord> {
+        self.ips
+            .iter()
+            .filter(|u| u.service().is_none())
+            .flat_map(|u| u.records.iter())
+    }
+}
+
+impl OvhIp {
+    /// Retrieves an IP block.
+    pub async fn get(
+        c: &OvhClient,
+        ip: &str,
+    ) -> Result<OvhIp, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/ip/{}", ip.replace('/', "%2F")))
+            .await?;
+        Ok(res)
+    }
+
+    /// Lists the IP blocks of the account.
+    pub async fn list_blocks(
+        c: &OvhClient,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json("/ip").await?;
+        Ok(res)
+    }
+
+    /// Checks whether an address belongs to the block.
+    ///
+    /// ```
+    /// use ovh::ip::OvhIp;
+    ///
+    /// let ip = OvhIp {
+    ///     ip: "203.0.113.0/28".into(),
+    ///     ip_type: "failover".into(),
+    ///     routed_to: None,
+    ///     description: None,
+    /// };
+    /// assert!(ip.contains("203.0.113.15".parse().unwrap()));
+    /// assert!(!ip.contains("203.0.113.16".parse().unwrap()));
+    /// ```
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        let (net, len) = match self.ip.split_once('/') {
+            Some((net, len)) => (net, len.parse().ok()),
+            None => (self.ip.as_str(), None),
+        };
+
+        match (net.parse(), addr) {
+            (Ok(IpAddr::V4(net)), IpAddr::V4(addr)) => {
+                let len: u32 = len.unwrap_or(32).min(32);
+                let mask = u32::MAX.checked_shl(32 - len).unwrap_or(0);
+                u32::from(net) & mask == u32::from(addr) & mask
+            }
+            (Ok(IpAddr::V6(net)), IpAddr::V6(addr)) => {
+                let len: u32 = len.unwrap_or(128).min(128);
+                let mask = u128::MAX.checked_shl(128 - len).unwrap_or(0);
+                u128::from(net) & mask == u128::from(addr) & mask
+            }
+            _ => false,
+        }
+    }
+
+    /// Maps every IP block of the account to the service it is routed to,
+    /// the dedicated servers and VPS using it and the A and AAAA records of
+    /// the account targeting it.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::ip::OvhIp;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let report = OvhIp::usage_report(&c).await.unwrap();
+    ///
+    ///     for u in &report.ips {
+    ///         println!("{} -> {:?} ({} records)", u.ip.ip, u.service(), u.records.len());
+    ///     }
+    ///     for r in report.dangling() {
+    ///         println!("dangling: {} {}", r.zone, r);
+    ///     }
+    ///     for r in &report.unmatched {
+    ///         println!("not an address of the account: {} {}", r.zone, r);
+    ///     }
+    /// }
+    /// ```
+    pub async fn usage_report(
+        c: &OvhClient,
+    ) -> Result<OvhIpUsageReport, Box<dyn std::error::Error + Send + Sync>> {
+        let blocks = Self::list_blocks(c).await?;
+        let (ips, records, servers) = futures::future::join3(
+            futures::future::join_all(blocks.iter().map(|b| Self::get(c, b))),
+            search_records(c, &[DnsRecordType::A, DnsRecordType::Aaaa], |_| true),
+            server_addresses(c),
+        )
+        .await;
+        let records = records?;
+        let (servers, failed) = servers;
+
+        let mut report = OvhIpUsageReport {
+            failed: records.failed,
+            ..Default::default()
+        };
+        report.failed.extend(failed);
+        for (block, ip) in blocks.into_iter().zip(ips) {
+            match ip {
+                Ok(ip) => {
+                    let mut names: Vec<String> = servers
+                        .iter()
+                        .filter(|(_, addr)| ip.contains(*addr))
+                        .map(|(name, _)| name.clone())
+                        .collect();
+                    names.dedup();
+                    report.ips.push(OvhIpUsage {
+                        servers: names,
+                        records: Vec::new(),
+                        ip,
+                    })
+                }
+                Err(e) => report.failed.push((block, e.to_string())),
+            }
+        }
+
+        for r in records.records {
+            let addr = r.target.trim().parse().ok();
+            let usage = addr.and_then(|a| report.ips.iter_mut().find(|u| u.ip.contains(a)));
+            match usage {
+                Some(u) => u.records.push(r),
+                None => report.unmatched.push(r),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Lists the addresses of the dedicated servers and VPS of the account,
+/// along with the servers or VPS that could not be inspected.
+async fn server_addresses(c: &OvhClient) -> (Vec<(String, IpAddr)>, Vec<(String, String)>) {
+    let mut addresses = Vec::new();
+    let mut failed = Vec::new();
+
+    let (servers, vps) =
+        futures::future::join(OvhDedicatedServer::list_names(c), OvhVps::list_names(c)).await;
+    let mut lists = Vec::new();
+    match servers {
+        Ok(servers) => {
+            let res = fetch_all(&servers, c.fetch_concurrency(), |s| {
+                OvhDedicatedServer::ips(c, s)
+            })
+            .await;
+            lists.extend(servers.into_iter().zip(res));
+        }
+        Err(e) => failed.push(("/dedicated/server".to_string(), e.to_string())),
+    }
+    match vps {
+        Ok(vps) => {
+            let res = fetch_all(&vps, c.fetch_concurrency(), |v| OvhVps::ips(c, v)).await;
+            lists.extend(vps.into_iter().zip(res));
+        }
+        Err(e) => failed.push(("/vps".to_string(), e.to_string())),
+    }
+
+    for (name, ips) in lists {
+        match ips {
+            Ok(ips) => addresses.extend(ips.iter().filter_map(|ip| {
+                let addr = ip.split('/').next()?.parse().ok()?;
+                Some((name.clone(), addr))
+            })),
+            Err(e) => failed.push((name, e.to_string())),
+        }
+    }
+
+    (addresses, failed)
+}
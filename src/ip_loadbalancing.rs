@@ -0,0 +1,263 @@
+//! High-level access to the IP Load Balancing API.
+
+use crate::client::OvhClient;
+
+use serde::{Deserialize, Serialize};
+
+/// Protocols of the frontends able to terminate TLS.
+const SSL_PROTOCOLS: [&str; 2] = ["http", "tcp"];
+
+/// Structure representing a certificate installed on a load balancer.
+#[derive(Debug, Deserialize)]
+pub struct OvhIpLoadbalancingSsl {
+    /// Unique identifier of the certificate
+    pub id: u64,
+    /// Human-readable name of the certificate
+    #[serde(rename(deserialize = "displayName"))]
+    pub display_name: Option<String>,
+    /// Kind of certificate (`built`, `custom`...)
+    #[serde(rename(deserialize = "type"))]
+    pub kind: String,
+    /// SHA-1 fingerprint of the certificate
+    pub fingerprint: String,
+    /// Serial number of the certificate
+    pub serial: String,
+    /// Subject of the certificate
+    pub subject: String,
+    /// Subject alternative names of the certificate
+    pub san: Vec<String>,
+    /// Expiration date of the certificate
+    #[serde(rename(deserialize = "expireDate"))]
+    pub expire_date: String,
+}
+
+/// Parameters of a certificate to upload, in PEM format.
+#[derive(Debug, Serialize)]
+pub struct OvhIpLoadbalancingSslCreate {
+    /// Certificate
+    pub certificate: String,
+    /// Private key of the certificate
+    pub key: String,
+    /// Intermediate certificates
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+    /// Human-readable name of the certificate
+    #[serde(
+        rename(serialize = "displayName"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub display_name: Option<String>,
+}
+
+impl OvhIpLoadbalancingSslCreate {
+    /// Creates the parameters of a certificate without chain nor name.
+    pub fn new(certificate: &str, key: &str) -> Self {
+        Self {
+            certificate: certificate.into(),
+            key: key.into(),
+            chain: None,
+            display_name: None,
+        }
+    }
+}
+
+/// Structure representing a frontend of a load balancer.
+#[derive(Debug, Deserialize)]
+pub struct OvhIpLoadbalancingFrontend {
+    /// Unique identifier of the frontend
+    #[serde(rename(deserialize = "frontendId"))]
+    pub frontend_id: u64,
+    /// Human-readable name of the frontend
+    #[serde(rename(deserialize = "displayName"))]
+    pub display_name: Option<String>,
+    /// Ports the frontend listens on
+    pub port: String,
+    /// Whether the frontend terminates TLS
+    pub ssl: bool,
+    /// Certificate served by default
+    #[serde(rename(deserialize = "defaultSslId"))]
+    pub default_ssl_id: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhIpLoadbalancingFrontendSsl {
+    #[serde(rename(serialize = "defaultSslId"))]
+    default_ssl_id: u64,
+}
+
+impl OvhIpLoadbalancingSsl {
+    /// Retrieves a certificate of a load balancer.
+    pub async fn get(
+        c: &OvhClient,
+        service: &str,
+        id: u64,
+    ) -> Result<OvhIpLoadbalancingSsl, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/ipLoadbalancing/{}/ssl/{}", service, id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Lists all of the certificates of a load balancer.
+    ///
+    /// This method will perform one extra API call per certificate
+    /// in order to get their details, and fails if any of them fails.
+    pub async fn list(
+        c: &OvhClient,
+        service: &str,
+    ) -> Result<Vec<OvhIpLoadbalancingSsl>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json::<Vec<u64>>(&format!("/ipLoadbalancing/{}/ssl", service))
+            .await?;
+        futures::future::try_join_all(res.iter().map(|&id| Self::get(c, service, id))).await
+    }
+
+    /// Uploads a certificate to a load balancer.
+    ///
+    /// The certificate is only served once a frontend uses it and the
+    /// configuration of the load balancer has been refreshed.
+    pub async fn create(
+        c: &OvhClient,
+        service: &str,
+        params: &OvhIpLoadbalancingSslCreate,
+    ) -> Result<OvhIpLoadbalancingSsl, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .post_json(&format!("/ipLoadbalancing/{}/ssl", service), params)
+            .await?;
+        Ok(res)
+    }
+
+    /// Deletes a certificate of a load balancer.
+    ///
+    /// Certificates still used by a frontend cannot be deleted.
+    pub async fn delete(
+        c: &OvhClient,
+        service: &str,
+        id: u64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.delete(&format!("/ipLoadbalancing/{}/ssl/{}", service, id))
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Replaces a certificate of a load balancer.
+    ///
+    /// The new certificate is uploaded, every HTTP and TCP frontend serving
+    /// the old one by default is switched to it, the configuration of the
+    /// load balancer is refreshed and the old certificate is deleted.
+    ///
+    /// If a step fails, the following ones are not performed: the new
+    /// certificate may then have to be cleaned up by hand.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::ip_loadbalancing::{OvhIpLoadbalancingSsl, OvhIpLoadbalancingSslCreate};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let cert = std::fs::read_to_string("cert.pem").unwrap();
+    ///     let key = std::fs::read_to_string("key.pem").unwrap();
+    ///     let mut params = OvhIpLoadbalancingSslCreate::new(&cert, &key);
+    ///     params.chain = Some(std::fs::read_to_string("chain.pem").unwrap());
+    ///
+    ///     let ssl = OvhIpLoadbalancingSsl::rotate_certificate(&c, "loadbalancer-xxx", 42, &params)
+    ///         .await
+    ///         .unwrap();
+    ///     println!("now serving {} until {}", ssl.id, ssl.expire_date);
+    /// }
+    /// ```
+    pub async fn rotate_certificate(
+        c: &OvhClient,
+        service: &str,
+        old_id: u64,
+        params: &OvhIpLoadbalancingSslCreate,
+    ) -> Result<OvhIpLoadbalancingSsl, Box<dyn std::error::Error + Send + Sync>> {
+        let new = Self::create(c, service, params).await?;
+
+        for protocol in SSL_PROTOCOLS.iter() {
+            let frontends = OvhIpLoadbalancingFrontend::list(c, service, protocol).await?;
+            for f in frontends
+                .iter()
+                .filter(|f| f.default_ssl_id == Some(old_id))
+            {
+                OvhIpLoadbalancingFrontend::set_default_ssl(
+                    c,
+                    service,
+                    protocol,
+                    f.frontend_id,
+                    new.id,
+                )
+                .await?;
+            }
+        }
+
+        c.post_empty(&format!("/ipLoadbalancing/{}/refresh", service))
+            .await?
+            .error_for_status()?;
+
+        Self::delete(c, service, old_id).await?;
+
+        Ok(new)
+    }
+}
+
+impl OvhIpLoadbalancingFrontend {
+    /// Retrieves a frontend of a load balancer.
+    ///
+    /// `protocol` is one of `http`, `tcp` or `udp`.
+    pub async fn get(
+        c: &OvhClient,
+        service: &str,
+        protocol: &str,
+        id: u64,
+    ) -> Result<OvhIpLoadbalancingFrontend, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!(
+                "/ipLoadbalancing/{}/{}/frontend/{}",
+                service, protocol, id
+            ))
+            .await?;
+        Ok(res)
+    }
+
+    /// Lists all of the frontends of a load balancer using a protocol.
+    ///
+    /// This method will perform one extra API call per frontend
+    /// in order to get their details, and fails if any of them fails.
+    pub async fn list(
+        c: &OvhClient,
+        service: &str,
+        protocol: &str,
+    ) -> Result<Vec<OvhIpLoadbalancingFrontend>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json::<Vec<u64>>(&format!(
+                "/ipLoadbalancing/{}/{}/frontend",
+                service, protocol
+            ))
+            .await?;
+        futures::future::try_join_all(res.iter().map(|&id| Self::get(c, service, protocol, id)))
+            .await
+    }
+
+    /// Sets the certificate served by default by a frontend.
+    pub async fn set_default_ssl(
+        c: &OvhClient,
+        service: &str,
+        protocol: &str,
+        id: u64,
+        ssl_id: u64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhIpLoadbalancingFrontendSsl {
+            default_ssl_id: ssl_id,
+        };
+        c.put(
+            &format!("/ipLoadbalancing/{}/{}/frontend/{}", service, protocol, id),
+            &data,
+        )
+        .await?
+        .error_for_status()?;
+        Ok(())
+    }
+}
@@ -1,4 +1,50 @@
 //! Async client for the OVH API.
 
+pub mod acme;
+pub mod bulk;
+pub mod cache;
 pub mod client;
+pub mod cloud_alerting;
+pub mod cloud_database;
+pub mod cloud_instance;
+pub mod cloud_project;
+pub mod cloud_storage;
+pub mod coalesce;
+pub mod config;
+mod date;
+pub mod dedicated_housing;
+pub mod dedicated_server;
+pub mod dns_owner;
+pub mod dns_record;
+pub mod dns_zone;
+pub mod domain_calendar;
 pub mod email_redir;
+pub mod endpoint;
+pub mod error;
+pub mod fetch;
+pub mod hosting_web;
+pub mod ip;
+pub mod ip_loadbalancing;
+pub mod me;
+pub mod metrics;
+pub mod middleware;
+pub mod okms;
+pub mod order;
+pub mod plan;
+pub mod policy;
+pub mod pool;
+pub mod rate_limit;
+pub mod retry;
+pub mod schema;
+pub mod secret;
+pub mod service;
+pub mod sms;
+pub mod ssl_inventory;
+pub mod status;
+#[cfg(feature = "test-util")]
+pub mod testing;
+pub mod timer;
+pub mod transport;
+pub mod vps;
+pub mod zone_backup;
+pub mod zone_reconciler;
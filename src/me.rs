@@ -0,0 +1,655 @@
+//! High-level access to the account (`/me`) API.
+
+use crate::client::OvhClient;
+use crate::order::OvhPrice;
+
+use serde::{Deserialize, Serialize};
+
+/// Origin of the API calls recorded in logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OvhApiLogScope {
+    /// Calls performed with the credentials of the account
+    Account,
+    /// Calls performed on the services of the account by other accounts,
+    /// such as delegated contacts
+    Services,
+}
+
+impl OvhApiLogScope {
+    fn as_path(&self) -> &'static str {
+        match self {
+            OvhApiLogScope::Account => "self",
+            OvhApiLogScope::Services => "services",
+        }
+    }
+}
+
+/// Structure representing an API call recorded in the logs of the account.
+#[derive(Debug, Deserialize)]
+pub struct OvhApiLog {
+    /// Unique identifier of the log entry
+    #[serde(rename(deserialize = "logId"))]
+    pub log_id: u64,
+    /// Date of the call
+    pub date: String,
+    /// Account (NIC handle) that performed the call
+    pub account: String,
+    /// IP address the call came from
+    pub ip: Option<String>,
+    /// HTTP method of the call
+    pub method: String,
+    /// Route of the call, such as `/domain/zone/{zoneName}/record`
+    pub route: String,
+    /// Actual path of the call
+    pub path: String,
+}
+
+impl OvhApiLog {
+    /// Lists the identifiers of the log entries of a scope, oldest first.
+    pub async fn list_ids(
+        c: &OvhClient,
+        scope: OvhApiLogScope,
+    ) -> Result<Vec<u64>, Box<dyn std::error::Error + Send + Sync>> {
+        let mut res: Vec<u64> = c
+            .get_json(&format!("/me/api/logs/{}", scope.as_path()))
+            .await?;
+        res.sort_unstable();
+        Ok(res)
+    }
+
+    /// Retrieves a log entry.
+    pub async fn get(
+        c: &OvhClient,
+        scope: OvhApiLogScope,
+        id: u64,
+    ) -> Result<OvhApiLog, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/me/api/logs/{}/{}", scope.as_path(), id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Retrieves the most recent log entries of a scope, oldest first.
+    ///
+    /// This method will perform one extra API call per entry
+    /// in order to get their details, and fails if any of them fails so
+    /// that no activity goes unnoticed.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::me::{OvhApiLog, OvhApiLogScope};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     for log in OvhApiLog::recent(&c, OvhApiLogScope::Account, 100).await.unwrap() {
+    ///         println!("{} {} {} {}", log.date, log.account, log.method, log.path);
+    ///     }
+    /// }
+    /// ```
+    pub async fn recent(
+        c: &OvhClient,
+        scope: OvhApiLogScope,
+        limit: usize,
+    ) -> Result<Vec<OvhApiLog>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids = Self::list_ids(c, scope).await?;
+        let ids = &ids[ids.len().saturating_sub(limit)..];
+
+        futures::future::try_join_all(ids.iter().map(|&id| Self::get(c, scope, id))).await
+    }
+}
+
+/// Structure representing a contact of the account.
+#[derive(Debug, Deserialize)]
+pub struct OvhContact {
+    /// Unique identifier of the contact
+    pub id: u64,
+    /// First name of the contact
+    #[serde(rename(deserialize = "firstName"))]
+    pub first_name: String,
+    /// Last name of the contact
+    #[serde(rename(deserialize = "lastName"))]
+    pub last_name: String,
+    /// Email address of the contact
+    pub email: String,
+    /// Phone number of the contact
+    pub phone: Option<String>,
+    /// Organisation of the contact, if any
+    #[serde(rename(deserialize = "organisationName"))]
+    pub organisation_name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhContactEmail<'a> {
+    email: &'a str,
+}
+
+impl OvhContact {
+    /// Retrieves a contact.
+    pub async fn get(
+        c: &OvhClient,
+        id: u64,
+    ) -> Result<OvhContact, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/me/contact/{}", id)).await?;
+        Ok(res)
+    }
+
+    /// Lists all of the contacts of the account.
+    ///
+    /// This method will perform one extra API call per contact
+    /// in order to get their details.
+    pub async fn list(
+        c: &OvhClient,
+    ) -> Result<Vec<OvhContact>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json::<Vec<u64>>("/me/contact").await?;
+        let res: Vec<_> = futures::future::join_all(res.iter().map(|&id| Self::get(c, id))).await;
+
+        let res = res.into_iter().filter_map(|c| c.ok()).collect();
+
+        Ok(res)
+    }
+
+    /// Changes the email address of a contact, where its notifications are
+    /// sent.
+    pub async fn set_email(
+        c: &OvhClient,
+        id: u64,
+        email: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhContactEmail { email };
+        c.put(&format!("/me/contact/{}", id), &data)
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Structure representing a request to change the contacts of a service.
+///
+/// The request only takes effect once accepted by the accounts involved,
+/// with the token they received by email.
+#[derive(Debug, Deserialize)]
+pub struct OvhContactChangeTask {
+    /// Unique identifier of the task
+    pub id: u64,
+    /// Service whose contacts change
+    #[serde(rename(deserialize = "serviceDomain"))]
+    pub service_domain: String,
+    /// Contacts being changed (`contactAdmin`, `contactTech`...)
+    #[serde(rename(deserialize = "contactTypes"))]
+    pub contact_types: Vec<String>,
+    /// Account (NIC handle) currently holding the contacts
+    #[serde(rename(deserialize = "fromAccount"))]
+    pub from_account: String,
+    /// Account (NIC handle) the contacts are given to
+    #[serde(rename(deserialize = "toAccount"))]
+    pub to_account: String,
+    /// Account (NIC handle) that requested the change
+    #[serde(rename(deserialize = "askingAccount"))]
+    pub asking_account: Option<String>,
+    /// Current state of the task (`todo`, `validatingByCustomers`, `done`,
+    /// `refused`...)
+    pub state: String,
+    /// Date of the request
+    #[serde(rename(deserialize = "dateRequest"))]
+    pub date_request: String,
+    /// Date the change was applied, if done
+    #[serde(rename(deserialize = "dateDone"))]
+    pub date_done: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhContactChangeToken<'a> {
+    token: &'a str,
+}
+
+impl OvhContactChangeTask {
+    /// Retrieves a contact change task.
+    pub async fn get(
+        c: &OvhClient,
+        id: u64,
+    ) -> Result<OvhContactChangeTask, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/me/task/contactChange/{}", id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Lists all of the contact change tasks involving the account.
+    ///
+    /// This method will perform one extra API call per task
+    /// in order to get their details.
+    pub async fn list(
+        c: &OvhClient,
+    ) -> Result<Vec<OvhContactChangeTask>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json::<Vec<u64>>("/me/task/contactChange").await?;
+        let res: Vec<_> = futures::future::join_all(res.iter().map(|&id| Self::get(c, id))).await;
+
+        let res = res.into_iter().filter_map(|c| c.ok()).collect();
+
+        Ok(res)
+    }
+
+    /// Accepts a contact change, with the token received by email.
+    pub async fn accept(
+        c: &OvhClient,
+        id: u64,
+        token: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhContactChangeToken { token };
+        c.post(&format!("/me/task/contactChange/{}/accept", id), &data)
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Refuses a contact change, with the token received by email.
+    pub async fn refuse(
+        c: &OvhClient,
+        id: u64,
+        token: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhContactChangeToken { token };
+        c.post(&format!("/me/task/contactChange/{}/refuse", id), &data)
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Sends the email holding the token of a contact change again.
+    pub async fn resend_email(
+        c: &OvhClient,
+        id: u64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        c.post_empty(&format!("/me/task/contactChange/{}/resendEmail", id))
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Structure representing a payment method registered on the account.
+#[derive(Debug, Deserialize)]
+pub struct OvhPaymentMethod {
+    /// Unique identifier of the payment method
+    #[serde(rename(deserialize = "paymentMethodId"))]
+    pub payment_method_id: u64,
+    /// Kind of payment method (`CREDIT_CARD`, `SEPA_DIRECT_DEBIT`...)
+    #[serde(rename(deserialize = "paymentType"))]
+    pub payment_type: String,
+    /// Current status (`VALID`, `PENDING_CHALLENGE`, `EXPIRED`...)
+    pub status: String,
+    /// Whether the payment method is the default one of the account
+    pub default: bool,
+    /// Masked identification of the payment method, such as a card number
+    pub label: Option<String>,
+    /// Description given when the payment method was registered
+    pub description: Option<String>,
+    /// Expiration date of the payment method, if it expires
+    #[serde(rename(deserialize = "expirationDate"))]
+    pub expiration_date: Option<String>,
+}
+
+/// Pages the user is sent back to once a payment method is registered.
+#[derive(Debug, Clone, Serialize)]
+pub struct OvhPaymentCallbackUrl {
+    /// Page shown when the registration succeeded
+    pub success: String,
+    /// Page shown when the registration failed
+    pub failure: String,
+    /// Page shown when the registration was cancelled
+    pub cancel: String,
+    /// Page shown while the registration is being processed
+    pub pending: String,
+    /// Page shown when the registration errored
+    pub error: String,
+}
+
+/// Structure representing a payment method being registered.
+#[derive(Debug, Deserialize)]
+pub struct OvhPaymentMethodValidation {
+    /// Unique identifier of the new payment method
+    #[serde(rename(deserialize = "paymentMethodId"))]
+    pub payment_method_id: u64,
+    /// How the registration must be completed (`REDIRECT`, `IFRAME_VANTIV`...)
+    #[serde(rename(deserialize = "validationType"))]
+    pub validation_type: String,
+    /// Page where the owner of the payment method completes the
+    /// registration, if any
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhPaymentMethodCreate<'a> {
+    #[serde(rename(serialize = "paymentType"))]
+    payment_type: &'a str,
+    #[serde(rename(serialize = "callbackUrl"))]
+    callback_url: &'a OvhPaymentCallbackUrl,
+    default: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhPaymentMethodChallenge<'a> {
+    challenge: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhPaymentMethodDefault {
+    default: bool,
+}
+
+impl OvhPaymentMethod {
+    /// Retrieves a payment method.
+    pub async fn get(
+        c: &OvhClient,
+        id: u64,
+    ) -> Result<OvhPaymentMethod, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/me/payment/method/{}", id)).await?;
+        Ok(res)
+    }
+
+    /// Lists all of the payment methods of the account.
+    ///
+    /// This method will perform one extra API call per payment method
+    /// in order to get their details.
+    pub async fn list(
+        c: &OvhClient,
+    ) -> Result<Vec<OvhPaymentMethod>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json::<Vec<u64>>("/me/payment/method").await?;
+        let res: Vec<_> = futures::future::join_all(res.iter().map(|&id| Self::get(c, id))).await;
+
+        let res = res.into_iter().filter_map(|c| c.ok()).collect();
+
+        Ok(res)
+    }
+
+    /// Returns the default payment method of the account, if any.
+    pub async fn default_method(
+        c: &OvhClient,
+    ) -> Result<Option<OvhPaymentMethod>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = Self::list(c).await?;
+        Ok(res.into_iter().find(|m| m.default))
+    }
+
+    /// Starts the registration of a payment method.
+    ///
+    /// The registration is then completed by the owner of the payment
+    /// method on the returned page, or with
+    /// [`OvhPaymentMethod::challenge`] for the methods validated by a
+    /// challenge.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::me::{OvhPaymentCallbackUrl, OvhPaymentMethod};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let page = "https://example.com/payment".to_string();
+    ///     let callback = OvhPaymentCallbackUrl {
+    ///         success: page.clone(),
+    ///         failure: page.clone(),
+    ///         cancel: page.clone(),
+    ///         pending: page.clone(),
+    ///         error: page,
+    ///     };
+    ///     let validation = OvhPaymentMethod::add(&c, "SEPA_DIRECT_DEBIT", &callback, true, None)
+    ///         .await
+    ///         .unwrap();
+    ///     println!("{:?}", validation.url);
+    /// }
+    /// ```
+    pub async fn add(
+        c: &OvhClient,
+        payment_type: &str,
+        callback_url: &OvhPaymentCallbackUrl,
+        default: bool,
+        description: Option<&str>,
+    ) -> Result<OvhPaymentMethodValidation, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhPaymentMethodCreate {
+            payment_type,
+            callback_url,
+            default,
+            description,
+        };
+        let res = c.post_json("/me/payment/method", &data).await?;
+        Ok(res)
+    }
+
+    /// Completes the registration of a payment method validated by a
+    /// challenge, such as the amount of a test transaction.
+    pub async fn challenge(
+        c: &OvhClient,
+        id: u64,
+        challenge: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhPaymentMethodChallenge { challenge };
+        c.post(&format!("/me/payment/method/{}/challenge", id), &data)
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Makes a payment method the default one of the account, used to pay
+    /// renewals and orders checked out with automatic payment.
+    pub async fn set_default(
+        c: &OvhClient,
+        id: u64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhPaymentMethodDefault { default: true };
+        c.put(&format!("/me/payment/method/{}", id), &data)
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Structure representing a prepaid credit balance of the account.
+#[derive(Debug, Deserialize)]
+pub struct OvhCreditBalance {
+    /// Name of the balance
+    #[serde(rename(deserialize = "balanceName"))]
+    pub balance_name: String,
+    /// Kind of balance (`PREPAID_ACCOUNT`, `VOUCHER`, `DEPOSIT`, `BONUS`)
+    #[serde(rename(deserialize = "type"))]
+    pub balance_type: String,
+    /// Amount available
+    pub amount: OvhPrice,
+    /// Date of the last change of the balance
+    #[serde(rename(deserialize = "lastUpdate"))]
+    pub last_update: String,
+}
+
+/// Structure representing a movement of a credit balance.
+#[derive(Debug, Deserialize)]
+pub struct OvhCreditMovement {
+    /// Unique identifier of the movement
+    #[serde(rename(deserialize = "movementId"))]
+    pub movement_id: u64,
+    /// Kind of movement (`CONSUMPTION`, `REFUND`, `GIFT`...)
+    #[serde(rename(deserialize = "type"))]
+    pub movement_type: String,
+    /// Amount of the movement, negative when credit is consumed
+    pub amount: OvhPrice,
+    /// Date of the movement
+    #[serde(rename(deserialize = "creationDate"))]
+    pub creation_date: String,
+    /// Date the credit expires, if it does
+    #[serde(rename(deserialize = "expirationDate"))]
+    pub expiration_date: Option<String>,
+    /// Order the movement is related to, if any
+    #[serde(rename(deserialize = "orderId"))]
+    pub order_id: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhCreditCode<'a> {
+    #[serde(rename(serialize = "inputCode"))]
+    input_code: &'a str,
+}
+
+impl OvhCreditBalance {
+    /// Retrieves a credit balance.
+    pub async fn get(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<OvhCreditBalance, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/me/credit/balance/{}", name)).await?;
+        Ok(res)
+    }
+
+    /// Lists all of the credit balances of the account.
+    ///
+    /// This method will perform one extra API call per balance
+    /// in order to get their details.
+    pub async fn list(
+        c: &OvhClient,
+    ) -> Result<Vec<OvhCreditBalance>, Box<dyn std::error::Error + Send + Sync>> {
+        let names = c.get_json::<Vec<String>>("/me/credit/balance").await?;
+        let res = futures::future::try_join_all(names.iter().map(|n| Self::get(c, n))).await?;
+        Ok(res)
+    }
+
+    /// Returns the credit available on the account, summed over every
+    /// balance.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::me::OvhCreditBalance;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     if OvhCreditBalance::available(&c).await.unwrap() < 50.0 {
+    ///         eprintln!("credit is running low, not provisioning");
+    ///     }
+    /// }
+    /// ```
+    pub async fn available(c: &OvhClient) -> Result<f64, Box<dyn std::error::Error + Send + Sync>> {
+        let res = Self::list(c).await?;
+        Ok(res.iter().map(|b| b.amount.value).sum())
+    }
+
+    /// Lists the movements of a credit balance.
+    ///
+    /// This method will perform one extra API call per movement
+    /// in order to get their details.
+    pub async fn movements(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<Vec<OvhCreditMovement>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids = c
+            .get_json::<Vec<u64>>(&format!("/me/credit/balance/{}/movement", name))
+            .await?;
+        let res = futures::future::try_join_all(ids.iter().map(|id| async move {
+            c.get_json::<OvhCreditMovement>(&format!("/me/credit/balance/{}/movement/{}", name, id))
+                .await
+        }))
+        .await?;
+        Ok(res)
+    }
+
+    /// Redeems a voucher code, returning the movement crediting the
+    /// account.
+    pub async fn redeem_voucher(
+        c: &OvhClient,
+        code: &str,
+    ) -> Result<OvhCreditMovement, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhCreditCode { input_code: code };
+        let res = c.post_json("/me/credit/code", &data).await?;
+        Ok(res)
+    }
+}
+
+/// Structure representing a consent campaign, such as a kind of marketing
+/// communication the account can opt in or out of.
+#[derive(Debug, Deserialize)]
+pub struct OvhConsentCampaign {
+    /// Name of the campaign
+    pub name: String,
+    /// Description of the campaign
+    pub description: String,
+    /// Kind of consent (`OPTIN`, `OPTOUT`)
+    #[serde(rename(deserialize = "type"))]
+    pub consent_type: String,
+}
+
+/// Structure representing the decision of the account about a consent
+/// campaign.
+#[derive(Debug, Deserialize)]
+pub struct OvhConsentDecision {
+    /// Name of the campaign
+    pub campaign: String,
+    /// Kind of consent (`OPTIN`, `OPTOUT`)
+    #[serde(rename(deserialize = "type"))]
+    pub consent_type: String,
+    /// Whether the account consents
+    pub value: bool,
+    /// Date of the decision
+    pub timestamp: String,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhConsentDecisionUpdate {
+    value: bool,
+}
+
+impl OvhConsentCampaign {
+    /// Retrieves a consent campaign.
+    pub async fn get(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<OvhConsentCampaign, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/me/consent/{}", name)).await?;
+        Ok(res)
+    }
+
+    /// Lists all of the consent campaigns of the account.
+    pub async fn list(
+        c: &OvhClient,
+    ) -> Result<Vec<OvhConsentCampaign>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json("/me/consent").await?;
+        Ok(res)
+    }
+
+    /// Retrieves the current decision of the account about a campaign.
+    pub async fn decision(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<OvhConsentDecision, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/me/consent/{}/decision", name))
+            .await?;
+        Ok(res)
+    }
+
+    /// Records the decision of the account about a campaign.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::me::OvhConsentCampaign;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     for campaign in OvhConsentCampaign::list(&c).await.unwrap() {
+    ///         OvhConsentCampaign::decide(&c, &campaign.name, false)
+    ///             .await
+    ///             .unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub async fn decide(
+        c: &OvhClient,
+        name: &str,
+        value: bool,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhConsentDecisionUpdate { value };
+        c.put(&format!("/me/consent/{}/decision", name), &data)
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
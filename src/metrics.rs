@@ -0,0 +1,342 @@
+//! Instrumentation of the requests sent by a client.
+//!
+//! A [`MetricsRecorder`] registered with
+//! [`OvhClient::with_metrics`](crate::client::OvhClient::with_metrics) is
+//! told about every request sent, retries included, with its route, status
+//! and latency. [`RouteMetrics`] is a ready-made recorder keeping counters
+//! and latency histograms per route, which can be rendered in the
+//! Prometheus text format.
+//!
+//! Responses of deprecated routes carry an [`OvhDeprecation`], so that
+//! users learn about upcoming removals from their own metrics and logs.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use reqwest::{header::HeaderMap, Method, StatusCode};
+
+/// Upper bounds of the latency histogram buckets, in seconds.
+const BUCKETS: [f64; 8] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// A request sent by a client.
+#[derive(Debug, Clone)]
+pub struct OvhRequestMetric<'a> {
+    /// HTTP method of the request
+    pub method: &'a Method,
+    /// Path of the request relative to the endpoint, such as
+    /// `/1.0/domain/zone/example.com`, without the query string
+    pub path: &'a str,
+    /// Status of the response, or `None` if no response was received
+    pub status: Option<StatusCode>,
+    /// Time elapsed until the response headers were received or the
+    /// request failed
+    pub duration: Duration,
+    /// Deprecation of the route announced by the response, if any
+    pub deprecation: Option<OvhDeprecation>,
+}
+
+impl OvhRequestMetric<'_> {
+    /// Whether the request failed, either without response or with an
+    /// error status.
+    pub fn is_error(&self) -> bool {
+        self.status
+            .is_none_or(|s| s.is_client_error() || s.is_server_error())
+    }
+}
+
+/// Deprecation of a route, announced by the headers of its responses.
+///
+/// Deprecated routes are flagged with the `Deprecation` header, and their
+/// removal date is announced with the `Sunset` header (RFC 9745 and
+/// RFC 8594). Both may point to documentation with a `Link` header.
+/// Deprecations are passed to the [`MetricsRecorder`] of the client, and
+/// can be logged by a middleware:
+///
+/// ```
+/// use ovh::client::OvhClient;
+/// use ovh::metrics::OvhDeprecation;
+/// use ovh::middleware::{ClientMiddleware, OvhRequest};
+///
+/// struct LogDeprecations;
+///
+/// impl ClientMiddleware for LogDeprecations {
+///     fn after_response(&self, req: &OvhRequest, resp: &reqwest::Response) {
+///         if let Some(d) = OvhDeprecation::from_headers(resp.headers()) {
+///             eprintln!("warning: {} {}: {}", req.method, req.url, d);
+///         }
+///     }
+/// }
+///
+/// let client = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key")
+///     .unwrap()
+///     .with_middleware(LogDeprecations);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OvhDeprecation {
+    /// Value of the `Deprecation` header, usually the date of the
+    /// deprecation as `@<timestamp>`, or `None` if only a sunset is
+    /// announced
+    pub deprecation: Option<String>,
+    /// Value of the `Sunset` header, the HTTP date after which the route
+    /// may stop answering
+    pub sunset: Option<String>,
+    /// Documentation of the deprecation or of the replacement route
+    pub link: Option<String>,
+}
+
+impl OvhDeprecation {
+    /// Reads the deprecation announced by the headers of a response, if
+    /// any.
+    ///
+    /// ```
+    /// use ovh::metrics::OvhDeprecation;
+    /// use reqwest::header::{HeaderMap, HeaderValue};
+    ///
+    /// let mut headers = HeaderMap::new();
+    /// assert_eq!(OvhDeprecation::from_headers(&headers), None);
+    ///
+    /// headers.insert("Deprecation", HeaderValue::from_static("@1735689600"));
+    /// headers.insert("Sunset", HeaderValue::from_static("Wed, 31 Dec 2025 23:59:59 GMT"));
+    /// headers.insert(
+    ///     "Link",
+    ///     HeaderValue::from_static(r#"<https://api.ovh.com/console>; rel="deprecation""#),
+    /// );
+    /// let d = OvhDeprecation::from_headers(&headers).unwrap();
+    /// assert_eq!(d.sunset.as_deref(), Some("Wed, 31 Dec 2025 23:59:59 GMT"));
+    /// assert_eq!(d.link.as_deref(), Some("https://api.ovh.com/console"));
+    /// assert_eq!(
+    ///     d.to_string(),
+    ///     "route deprecated since @1735689600, removed after Wed, 31 Dec 2025 23:59:59 GMT \
+    ///      (see https://api.ovh.com/console)"
+    /// );
+    /// ```
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let value = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+        };
+        let deprecation = value("Deprecation").filter(|v| v != "false");
+        let sunset = value("Sunset");
+        if deprecation.is_none() && sunset.is_none() {
+            return None;
+        }
+
+        let link = headers
+            .get_all("Link")
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .find_map(|l| {
+                let (target, params) = l.trim().strip_prefix('<')?.split_once('>')?;
+                let rel = params
+                    .split(';')
+                    .filter_map(|p| p.trim().strip_prefix("rel="))
+                    .next()?
+                    .trim_matches('"');
+                matches!(rel, "deprecation" | "sunset").then(|| target.to_string())
+            });
+
+        Some(Self {
+            deprecation,
+            sunset,
+            link,
+        })
+    }
+}
+
+impl fmt::Display for OvhDeprecation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "route deprecated")?;
+        if let Some(d) = self.deprecation.as_deref().filter(|d| *d != "true") {
+            write!(f, " since {}", d)?;
+        }
+        if let Some(s) = &self.sunset {
+            write!(f, ", removed after {}", s)?;
+        }
+        if let Some(l) = &self.link {
+            write!(f, " (see {})", l)?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives the metrics of the requests sent by a client.
+///
+/// Paths include the names of the resources, so they should be grouped,
+/// for instance by replacing names with placeholders, before being used
+/// as metric labels in systems sensitive to cardinality.
+///
+/// Any closure taking an [`OvhRequestMetric`] is a recorder:
+///
+/// ```
+/// use ovh::client::OvhClient;
+/// use ovh::metrics::OvhRequestMetric;
+///
+/// let client = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key")
+///     .unwrap()
+///     .with_metrics(|m: &OvhRequestMetric| {
+///         eprintln!("{} {} {:?} in {:?}", m.method, m.path, m.status, m.duration);
+///     });
+/// ```
+pub trait MetricsRecorder: Send + Sync {
+    /// Records a request.
+    fn record(&self, metric: &OvhRequestMetric);
+}
+
+impl<F> MetricsRecorder for F
+where
+    F: Fn(&OvhRequestMetric) + Send + Sync,
+{
+    fn record(&self, metric: &OvhRequestMetric) {
+        self(metric)
+    }
+}
+
+/// Statistics of the requests sent to a route.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RouteStats {
+    /// Number of requests
+    pub count: u64,
+    /// Number of failed requests
+    pub errors: u64,
+    /// Number of requests answered with a deprecation notice
+    pub deprecated: u64,
+    /// Sum of the latencies, in seconds
+    pub total_seconds: f64,
+    /// Number of requests per latency bucket, the last one counting the
+    /// requests slower than every bound
+    pub buckets: [u64; BUCKETS.len() + 1],
+}
+
+/// Recorder keeping counters and latency histograms per method and path.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use ovh::client::OvhClient;
+/// use ovh::metrics::RouteMetrics;
+///
+/// let metrics = Arc::new(RouteMetrics::new());
+/// let client = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key")
+///     .unwrap()
+///     .with_metrics(metrics.clone());
+///
+/// // Later, from the handler of the metrics endpoint:
+/// let body = metrics.render_prometheus();
+/// ```
+#[derive(Debug, Default)]
+pub struct RouteMetrics {
+    routes: Mutex<BTreeMap<(String, String), RouteStats>>,
+}
+
+impl RouteMetrics {
+    /// Creates a recorder without any request.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the statistics of every route, keyed by method and path.
+    pub fn snapshot(&self) -> BTreeMap<(String, String), RouteStats> {
+        self.routes.lock().unwrap().clone()
+    }
+
+    /// Renders the statistics in the Prometheus text format, as the
+    /// `ovh_api_requests_total`, `ovh_api_request_errors_total`,
+    /// `ovh_api_deprecated_requests_total` and
+    /// `ovh_api_request_duration_seconds` metrics.
+    pub fn render_prometheus(&self) -> String {
+        let routes = self.routes.lock().unwrap();
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# TYPE ovh_api_requests_total counter");
+        for ((method, path), s) in routes.iter() {
+            let _ = writeln!(
+                out,
+                "ovh_api_requests_total{{method=\"{}\",path=\"{}\"}} {}",
+                method, path, s.count
+            );
+        }
+
+        let _ = writeln!(out, "# TYPE ovh_api_request_errors_total counter");
+        for ((method, path), s) in routes.iter() {
+            let _ = writeln!(
+                out,
+                "ovh_api_request_errors_total{{method=\"{}\",path=\"{}\"}} {}",
+                method, path, s.errors
+            );
+        }
+
+        let _ = writeln!(out, "# TYPE ovh_api_deprecated_requests_total counter");
+        for ((method, path), s) in routes.iter() {
+            let _ = writeln!(
+                out,
+                "ovh_api_deprecated_requests_total{{method=\"{}\",path=\"{}\"}} {}",
+                method, path, s.deprecated
+            );
+        }
+
+        let _ = writeln!(out, "# TYPE ovh_api_request_duration_seconds histogram");
+        for ((method, path), s) in routes.iter() {
+            let mut cumulative = 0;
+            for (i, n) in s.buckets.iter().enumerate() {
+                cumulative += n;
+                let le = BUCKETS
+                    .get(i)
+                    .map_or_else(|| "+Inf".to_string(), |b| b.to_string());
+                let _ = writeln!(
+                    out,
+                    "ovh_api_request_duration_seconds_bucket{{method=\"{}\",path=\"{}\",le=\"{}\"}} {}",
+                    method, path, le, cumulative
+                );
+            }
+            let _ = writeln!(
+                out,
+                "ovh_api_request_duration_seconds_sum{{method=\"{}\",path=\"{}\"}} {}",
+                method, path, s.total_seconds
+            );
+            let _ = writeln!(
+                out,
+                "ovh_api_request_duration_seconds_count{{method=\"{}\",path=\"{}\"}} {}",
+                method, path, s.count
+            );
+        }
+
+        out
+    }
+}
+
+impl MetricsRecorder for RouteMetrics {
+    fn record(&self, metric: &OvhRequestMetric) {
+        let seconds = metric.duration.as_secs_f64();
+        let bucket = BUCKETS
+            .iter()
+            .position(|&b| seconds <= b)
+            .unwrap_or(BUCKETS.len());
+
+        let mut routes = self.routes.lock().unwrap();
+        let s = routes
+            .entry((metric.method.to_string(), metric.path.to_string()))
+            .or_default();
+        s.count += 1;
+        if metric.is_error() {
+            s.errors += 1;
+        }
+        if metric.deprecation.is_some() {
+            s.deprecated += 1;
+        }
+        s.total_seconds += seconds;
+        s.buckets[bucket] += 1;
+    }
+}
+
+impl<T: MetricsRecorder + ?Sized> MetricsRecorder for Arc<T> {
+    fn record(&self, metric: &OvhRequestMetric) {
+        (**self).record(metric)
+    }
+}
@@ -0,0 +1,186 @@
+//! Hooks run around the requests sent by a client.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+use reqwest::{header::HeaderMap, Method, Response};
+use secrecy::{ExposeSecret, SecretString};
+use sha2::{Digest, Sha256};
+
+use crate::error::OvhError;
+
+/// Request about to be sent by a client.
+#[derive(Debug, Clone)]
+pub struct OvhRequest {
+    /// HTTP method
+    pub method: Method,
+    /// Full URL, including the query string
+    pub url: String,
+    /// Headers
+    pub headers: HeaderMap,
+    /// JSON body, empty for requests without one
+    pub body: String,
+    /// Time of the API server when the request is sent, in seconds since
+    /// the Unix epoch, as estimated from the time delta of the client
+    pub timestamp: i64,
+}
+
+/// Hook run around every request sent by a client.
+///
+/// Middlewares are registered with
+/// [`OvhClient::with_middleware`](crate::client::OvhClient::with_middleware)
+/// and run in registration order. Authenticated requests are signed after
+/// every middleware has run, so changes to the URL or body are covered by
+/// the signature.
+///
+/// ```
+/// use ovh::client::OvhClient;
+/// use ovh::middleware::{ClientMiddleware, OvhRequest};
+///
+/// struct Audit;
+///
+/// impl ClientMiddleware for Audit {
+///     fn before_request(&self, req: &mut OvhRequest) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///         req.headers.insert("X-Request-Origin", "billing-sync".parse()?);
+///         Ok(())
+///     }
+///
+///     fn after_response(&self, req: &OvhRequest, resp: &reqwest::Response) {
+///         eprintln!("{} {} -> {}", req.method, req.url, resp.status());
+///     }
+/// }
+///
+/// let client = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key")
+///     .unwrap()
+///     .with_middleware(Audit);
+/// ```
+pub trait ClientMiddleware: Send + Sync {
+    /// Inspects or modifies a request before it is sent.
+    ///
+    /// Returning an error aborts the request with that error.
+    fn before_request(
+        &self,
+        _req: &mut OvhRequest,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        Ok(())
+    }
+
+    /// Inspects the response to a request.
+    ///
+    /// Only called when a response was received, whatever its status.
+    fn after_response(&self, _req: &OvhRequest, _resp: &Response) {}
+}
+
+/// Hash function used to sign the requests.
+///
+/// ```
+/// use ovh::client::OvhClient;
+/// use ovh::middleware::SignatureScheme;
+///
+/// let client = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key")
+///     .unwrap()
+///     .with_signature_scheme(SignatureScheme::Auto);
+/// assert_eq!(client.signature_scheme(), SignatureScheme::Auto);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignatureScheme {
+    /// SHA-1, as `$1$<hex digest>`, supported by every endpoint
+    #[default]
+    Sha1,
+    /// SHA-256, as `$2$<hex digest>`
+    Sha256,
+    /// SHA-256, falling back to SHA-1 for the rest of the life of the
+    /// client the first time the API rejects a signature
+    Auto,
+}
+
+/// Middleware adding the authentication headers of the OVH API.
+///
+/// The keys are wiped from memory when the signer is dropped, and
+/// redacted from its `Debug` output.
+#[derive(Debug, Clone)]
+pub(crate) struct Signer {
+    pub(crate) application_secret: SecretString,
+    pub(crate) consumer_key: SecretString,
+    pub(crate) scheme: SignatureScheme,
+    /// Whether [`SignatureScheme::Auto`] fell back to SHA-1, shared by the
+    /// clones of the client.
+    pub(crate) sha1_fallback: Arc<AtomicBool>,
+}
+
+impl Signer {
+    pub(crate) fn new(application_secret: SecretString, consumer_key: SecretString) -> Self {
+        Self {
+            application_secret,
+            consumer_key,
+            scheme: SignatureScheme::default(),
+            sha1_fallback: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Switches [`SignatureScheme::Auto`] to SHA-1, returning whether it
+    /// was still using SHA-256.
+    pub(crate) fn fall_back_to_sha1(&self) -> bool {
+        self.scheme == SignatureScheme::Auto && !self.sha1_fallback.swap(true, Ordering::Relaxed)
+    }
+
+    pub(crate) fn signature(&self, url: &str, timestamp: &str, method: &str, body: &str) -> String {
+        let values = [
+            self.application_secret.expose_secret().as_str(),
+            self.consumer_key.expose_secret().as_str(),
+            method,
+            url,
+            body,
+            timestamp,
+        ];
+        let data = values.join("+");
+        let sha256 = match self.scheme {
+            SignatureScheme::Sha1 => false,
+            SignatureScheme::Sha256 => true,
+            SignatureScheme::Auto => !self.sha1_fallback.load(Ordering::Relaxed),
+        };
+        if sha256 {
+            format!("$2${:x}", Sha256::digest(data.as_bytes()))
+        } else {
+            format!("$1${}", sha1::Sha1::from(data).hexdigest())
+        }
+    }
+}
+
+impl ClientMiddleware for Signer {
+    fn before_request(
+        &self,
+        req: &mut OvhRequest,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        // The API checks the signature against the URL as received, so the
+        // URL is normalized the way the HTTP client encodes it before being
+        // sent: spaces and non-ASCII characters get percent-encoded.
+        req.url = reqwest::Url::parse(&req.url)?.into();
+
+        let timestamp = req.timestamp.to_string();
+        let signature = self.signature(&req.url, &timestamp, req.method.as_str(), &req.body);
+
+        insert_sensitive_header(
+            &mut req.headers,
+            "X-Ovh-Consumer",
+            self.consumer_key.expose_secret(),
+        )?;
+        insert_sensitive_header(&mut req.headers, "X-Ovh-Timestamp", &timestamp)?;
+        insert_sensitive_header(&mut req.headers, "X-Ovh-Signature", &signature)?;
+        Ok(())
+    }
+}
+
+fn insert_sensitive_header(
+    headers: &mut HeaderMap,
+    header_name: &'static str,
+    value: &str,
+) -> Result<(), OvhError> {
+    let mut header_value = reqwest::header::HeaderValue::from_str(value)
+        .map_err(|_| OvhError::InvalidHeaderValue(header_name))?;
+    header_value.set_sensitive(true);
+    headers.insert(header_name, header_value);
+    Ok(())
+}
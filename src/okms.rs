@@ -0,0 +1,361 @@
+//! High-level access to the Key Management Service (OKMS) API.
+//!
+//! KMS routes are only served by the v2 API: paths are given with an
+//! explicit `/v2/` prefix, so they work whatever the
+//! [`ApiVersion`](crate::client::ApiVersion) of the client.
+
+use crate::client::OvhClient;
+
+use futures::TryStreamExt;
+use secrecy::SecretString;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Structure representing a KMS of the account.
+#[derive(Debug, Deserialize)]
+pub struct OvhOkms {
+    /// Unique identifier of the KMS
+    pub id: String,
+    /// Region hosting the KMS
+    pub region: String,
+    /// URL of the REST API of the KMS, used with credentials
+    #[serde(rename(deserialize = "restEndpoint"))]
+    pub rest_endpoint: Option<String>,
+    /// Address of the KMIP endpoint of the KMS
+    #[serde(rename(deserialize = "kmipEndpoint"))]
+    pub kmip_endpoint: Option<String>,
+}
+
+impl OvhOkms {
+    /// Lists the KMS of the account.
+    pub async fn list(
+        c: &OvhClient,
+    ) -> Result<Vec<OvhOkms>, Box<dyn std::error::Error + Send + Sync>> {
+        c.get_paginated("/v2/okms/resource").try_collect().await
+    }
+
+    /// Retrieves a KMS.
+    pub async fn get(
+        c: &OvhClient,
+        id: &str,
+    ) -> Result<OvhOkms, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/v2/okms/resource/{}", id)).await?;
+        Ok(res)
+    }
+}
+
+/// Type of a service key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OvhOkmsKeyType {
+    /// Symmetric key
+    #[serde(rename = "oct")]
+    Oct,
+    /// RSA key pair
+    #[serde(rename = "RSA")]
+    Rsa,
+    /// Elliptic curve key pair
+    #[serde(rename = "EC")]
+    Ec,
+}
+
+/// Structure representing a service key, used by other OVH products to
+/// encrypt their data.
+#[derive(Debug, Deserialize)]
+pub struct OvhOkmsServiceKey {
+    /// Unique identifier of the key
+    pub id: String,
+    /// Name of the key
+    pub name: String,
+    /// Type of the key
+    #[serde(rename(deserialize = "type"))]
+    pub key_type: OvhOkmsKeyType,
+    /// Size of the key, in bits, for symmetric and RSA keys
+    pub size: Option<u32>,
+    /// Curve of the key, for elliptic curve keys
+    pub curve: Option<String>,
+    /// Operations allowed with the key (`encrypt`, `sign`...)
+    #[serde(default)]
+    pub operations: Vec<String>,
+    /// State of the key (`ACTIVE`, `DEACTIVATED`...)
+    pub state: String,
+    /// Creation date of the key
+    #[serde(rename(deserialize = "createdAt"))]
+    pub created_at: String,
+}
+
+/// Parameters of a service key to create.
+#[derive(Debug, Clone, Serialize)]
+pub struct OvhOkmsServiceKeyCreate {
+    /// Name of the key
+    pub name: String,
+    /// Type of the key
+    #[serde(rename(serialize = "type"))]
+    pub key_type: OvhOkmsKeyType,
+    /// Size of the key, in bits, for symmetric and RSA keys
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u32>,
+    /// Curve of the key, for elliptic curve keys (`P-256`...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub curve: Option<String>,
+    /// Operations allowed with the key
+    pub operations: Vec<String>,
+}
+
+impl OvhOkmsServiceKeyCreate {
+    /// Creates the parameters of a 256-bit symmetric key, allowed to
+    /// encrypt and decrypt.
+    ///
+    /// ```
+    /// use ovh::okms::OvhOkmsServiceKeyCreate;
+    ///
+    /// let params = OvhOkmsServiceKeyCreate::symmetric("backups");
+    /// assert_eq!(
+    ///     serde_json::to_string(&params).unwrap(),
+    ///     r#"{"name":"backups","type":"oct","size":256,"operations":["encrypt","decrypt"]}"#
+    /// );
+    /// ```
+    pub fn symmetric(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            key_type: OvhOkmsKeyType::Oct,
+            size: Some(256),
+            curve: None,
+            operations: vec!["encrypt".into(), "decrypt".into()],
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct OvhOkmsServiceKeyUpdate<'a> {
+    name: &'a str,
+}
+
+impl OvhOkmsServiceKey {
+    /// Lists the service keys of a KMS.
+    pub async fn list(
+        c: &OvhClient,
+        okms: &str,
+    ) -> Result<Vec<OvhOkmsServiceKey>, Box<dyn std::error::Error + Send + Sync>> {
+        let path = format!("/v2/okms/resource/{}/serviceKey", okms);
+        c.get_paginated(&path).try_collect().await
+    }
+
+    /// Retrieves a service key.
+    pub async fn get(
+        c: &OvhClient,
+        okms: &str,
+        id: &str,
+    ) -> Result<OvhOkmsServiceKey, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/v2/okms/resource/{}/serviceKey/{}", okms, id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Creates a service key.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::okms::{OvhOkmsServiceKey, OvhOkmsServiceKeyCreate};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let params = OvhOkmsServiceKeyCreate::symmetric("backups");
+    ///     let key = OvhOkmsServiceKey::create(&c, "okms_id", &params)
+    ///         .await
+    ///         .unwrap();
+    ///     println!("created key {}", key.id);
+    /// }
+    /// ```
+    pub async fn create(
+        c: &OvhClient,
+        okms: &str,
+        params: &OvhOkmsServiceKeyCreate,
+    ) -> Result<OvhOkmsServiceKey, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .post_json(&format!("/v2/okms/resource/{}/serviceKey", okms), params)
+            .await?;
+        Ok(res)
+    }
+
+    /// Renames a service key.
+    pub async fn rename(
+        c: &OvhClient,
+        okms: &str,
+        id: &str,
+        name: &str,
+    ) -> Result<OvhOkmsServiceKey, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .put_json(
+                &format!("/v2/okms/resource/{}/serviceKey/{}", okms, id),
+                &OvhOkmsServiceKeyUpdate { name },
+            )
+            .await?;
+        Ok(res)
+    }
+
+    /// Deletes a service key.
+    ///
+    /// Data encrypted with the key can no longer be decrypted.
+    pub async fn delete(
+        c: &OvhClient,
+        okms: &str,
+        id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let resp = c
+            .delete(&format!("/v2/okms/resource/{}/serviceKey/{}", okms, id))
+            .await?;
+        OvhClient::ensure_success(resp).await?;
+        Ok(())
+    }
+}
+
+/// Structure representing a credential, a client certificate giving
+/// access to the REST and KMIP endpoints of a KMS.
+#[derive(Debug, Deserialize)]
+pub struct OvhOkmsCredential {
+    /// Unique identifier of the credential
+    pub id: String,
+    /// Name of the credential
+    pub name: String,
+    /// Description of the credential
+    pub description: Option<String>,
+    /// IAM identities allowed to use the credential
+    #[serde(rename(deserialize = "identityURNs"), default)]
+    pub identity_urns: Vec<String>,
+    /// Status of the credential (`CREATING`, `READY`, `EXPIRED`...)
+    pub status: String,
+    /// Whether the credential was created from a certificate signing
+    /// request
+    #[serde(rename(deserialize = "fromCSR"), default)]
+    pub from_csr: bool,
+    /// Certificate of the credential, once it is ready
+    #[serde(rename(deserialize = "certificatePEM"))]
+    pub certificate_pem: Option<String>,
+    /// Private key of the credential, only returned when it is created
+    /// without a certificate signing request
+    #[serde(
+        rename(deserialize = "privateKeyPEM"),
+        default,
+        deserialize_with = "deserialize_secret"
+    )]
+    pub private_key_pem: Option<SecretString>,
+    /// Creation date of the credential
+    #[serde(rename(deserialize = "createdAt"))]
+    pub created_at: String,
+    /// Expiration date of the credential
+    #[serde(rename(deserialize = "expiredAt"))]
+    pub expired_at: Option<String>,
+}
+
+fn deserialize_secret<'de, D>(d: D) -> Result<Option<SecretString>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(d)?.map(SecretString::new))
+}
+
+/// Parameters of a credential to create.
+#[derive(Debug, Clone, Serialize)]
+pub struct OvhOkmsCredentialCreate {
+    /// Name of the credential
+    pub name: String,
+    /// Description of the credential
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// IAM identities allowed to use the credential, such as
+    /// `urn:v1:eu:identity:account:xx1111-ovh`
+    #[serde(rename(serialize = "identityURNs"))]
+    pub identity_urns: Vec<String>,
+    /// Validity of the credential, in days
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validity: Option<u32>,
+    /// Certificate signing request, so that the private key never leaves
+    /// the caller
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub csr: Option<String>,
+}
+
+impl OvhOkmsCredentialCreate {
+    /// Creates the parameters of a credential for the given identities,
+    /// whose private key is generated by the API.
+    pub fn new(name: &str, identity_urns: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            identity_urns: identity_urns.iter().map(|&u| u.into()).collect(),
+            validity: None,
+            csr: None,
+        }
+    }
+}
+
+impl OvhOkmsCredential {
+    /// Lists the credentials of a KMS.
+    pub async fn list(
+        c: &OvhClient,
+        okms: &str,
+    ) -> Result<Vec<OvhOkmsCredential>, Box<dyn std::error::Error + Send + Sync>> {
+        let path = format!("/v2/okms/resource/{}/credential", okms);
+        c.get_paginated(&path).try_collect().await
+    }
+
+    /// Retrieves a credential.
+    pub async fn get(
+        c: &OvhClient,
+        okms: &str,
+        id: &str,
+    ) -> Result<OvhOkmsCredential, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/v2/okms/resource/{}/credential/{}", okms, id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Creates a credential.
+    ///
+    /// Without a certificate signing request, the private key is only
+    /// returned by this call.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::okms::{OvhOkmsCredential, OvhOkmsCredentialCreate};
+    /// use secrecy::ExposeSecret;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let params =
+    ///         OvhOkmsCredentialCreate::new("backup-agent", &["urn:v1:eu:identity:account:xx1111-ovh"]);
+    ///     let cred = OvhOkmsCredential::create(&c, "okms_id", &params)
+    ///         .await
+    ///         .unwrap();
+    ///     if let Some(key) = &cred.private_key_pem {
+    ///         std::fs::write("backup-agent.key", key.expose_secret()).unwrap();
+    ///     }
+    /// }
+    /// ```
+    pub async fn create(
+        c: &OvhClient,
+        okms: &str,
+        params: &OvhOkmsCredentialCreate,
+    ) -> Result<OvhOkmsCredential, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .post_json(&format!("/v2/okms/resource/{}/credential", okms), params)
+            .await?;
+        Ok(res)
+    }
+
+    /// Revokes a credential.
+    pub async fn delete(
+        c: &OvhClient,
+        okms: &str,
+        id: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let resp = c
+            .delete(&format!("/v2/okms/resource/{}/credential/{}", okms, id))
+            .await?;
+        OvhClient::ensure_success(resp).await?;
+        Ok(())
+    }
+}
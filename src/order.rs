@@ -0,0 +1,330 @@
+//! Types shared by the ordering routes, and tracking of placed orders.
+
+use std::time::{Duration, Instant};
+
+use crate::client::OvhClient;
+
+use serde::{Deserialize, Serialize};
+
+/// Delay between two status checks in
+/// [`OvhPlacedOrder::wait_until_delivered`].
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Structure representing an order, or the quote of an order.
+#[derive(Debug, Deserialize)]
+pub struct OvhOrder {
+    /// Unique identifier of the order, absent from quotes
+    #[serde(rename(deserialize = "orderId"))]
+    pub order_id: Option<u64>,
+    /// URL to pay the order, absent from quotes
+    pub url: Option<String>,
+    /// Total price of the order
+    pub prices: OvhOrderPrices,
+}
+
+/// Structure representing the total price of an order.
+#[derive(Debug, Deserialize)]
+pub struct OvhOrderPrices {
+    /// Price with taxes
+    #[serde(rename(deserialize = "withTax"))]
+    pub with_tax: OvhPrice,
+    /// Price without taxes
+    #[serde(rename(deserialize = "withoutTax"))]
+    pub without_tax: OvhPrice,
+}
+
+/// Structure representing an amount of money.
+#[derive(Debug, Deserialize)]
+pub struct OvhPrice {
+    /// Amount
+    pub value: f64,
+    /// Currency of the amount
+    #[serde(rename(deserialize = "currencyCode"))]
+    pub currency_code: String,
+    /// Amount formatted for display
+    pub text: String,
+}
+
+/// Structure representing an order placed by the account.
+#[derive(Debug, Deserialize)]
+pub struct OvhPlacedOrder {
+    /// Unique identifier of the order
+    #[serde(rename(deserialize = "orderId"))]
+    pub order_id: u64,
+    /// Date the order was placed
+    pub date: String,
+    /// Date after which the order cannot be paid anymore
+    #[serde(rename(deserialize = "expirationDate"))]
+    pub expiration_date: Option<String>,
+    /// URL to pay the order
+    pub url: String,
+    /// URL of the order form, as a PDF
+    #[serde(rename(deserialize = "pdfUrl"))]
+    pub pdf_url: String,
+    /// Price with taxes
+    #[serde(rename(deserialize = "priceWithTax"))]
+    pub price_with_tax: OvhPrice,
+    /// Price without taxes
+    #[serde(rename(deserialize = "priceWithoutTax"))]
+    pub price_without_tax: OvhPrice,
+}
+
+/// Processing status of an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OvhOrderStatus {
+    /// The order is waiting for its payment
+    NotPaid,
+    /// The order is being checked by OVH
+    Checking,
+    /// OVH needs documents to process the order
+    DocumentsRequested,
+    /// The resources of the order are being delivered
+    Delivering,
+    /// The resources of the order are delivered
+    Delivered,
+    /// The order is being cancelled
+    Cancelling,
+    /// The order was cancelled
+    Cancelled,
+    /// Any other status
+    #[serde(other)]
+    Unknown,
+}
+
+/// Structure representing a line of an order.
+#[derive(Debug, Deserialize)]
+pub struct OvhOrderDetail {
+    /// Unique identifier of the line
+    #[serde(rename(deserialize = "orderDetailId"))]
+    pub order_detail_id: u64,
+    /// Description of the ordered product
+    pub description: String,
+    /// Name of the service the line applies to, once delivered
+    pub domain: String,
+    /// Kind of line (`DURATION`, `INSTALLATION`...)
+    #[serde(rename(deserialize = "detailType"))]
+    pub detail_type: Option<String>,
+    /// Number of ordered items
+    pub quantity: String,
+    /// Total price of the line
+    #[serde(rename(deserialize = "totalPrice"))]
+    pub total_price: OvhPrice,
+}
+
+/// Structure representing the object an order is attached to, such as its
+/// bill once paid.
+#[derive(Debug, Deserialize)]
+pub struct OvhOrderAssociatedObject {
+    /// Identifier of the object
+    pub id: Option<String>,
+    /// Kind of object (`Bill`, `Deposit`, `Refund`...)
+    #[serde(rename(deserialize = "type"))]
+    pub object_type: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhOrderPay {
+    #[serde(rename(serialize = "paymentMethod"))]
+    payment_method: OvhOrderPaymentMethod,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhOrderPaymentMethod {
+    id: u64,
+}
+
+impl OvhPlacedOrder {
+    /// Retrieves an order.
+    pub async fn get(
+        c: &OvhClient,
+        id: u64,
+    ) -> Result<OvhPlacedOrder, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/me/order/{}", id)).await?;
+        Ok(res)
+    }
+
+    /// Retrieves the processing status of an order.
+    pub async fn status(
+        c: &OvhClient,
+        id: u64,
+    ) -> Result<OvhOrderStatus, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/me/order/{}/status", id)).await?;
+        Ok(res)
+    }
+
+    /// Lists the lines of an order.
+    ///
+    /// This method will perform one extra API call per line
+    /// in order to get their details.
+    pub async fn details(
+        c: &OvhClient,
+        id: u64,
+    ) -> Result<Vec<OvhOrderDetail>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids = c
+            .get_json::<Vec<u64>>(&format!("/me/order/{}/details", id))
+            .await?;
+        let res = futures::future::try_join_all(ids.iter().map(|detail| async move {
+            c.get_json::<OvhOrderDetail>(&format!("/me/order/{}/details/{}", id, detail))
+                .await
+        }))
+        .await?;
+        Ok(res)
+    }
+
+    /// Retrieves the object an order is attached to.
+    pub async fn associated_object(
+        c: &OvhClient,
+        id: u64,
+    ) -> Result<OvhOrderAssociatedObject, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/me/order/{}/associatedObject", id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Pays an order with a payment method registered on the account.
+    pub async fn pay(
+        c: &OvhClient,
+        id: u64,
+        payment_method_id: u64,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhOrderPay {
+            payment_method: OvhOrderPaymentMethod {
+                id: payment_method_id,
+            },
+        };
+        c.post(&format!("/me/order/{}/pay", id), &data)
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Waits until the resources of an order are delivered, then returns
+    /// the names of the delivered services.
+    ///
+    /// Returns an error if the order is cancelled, or if it is not
+    /// delivered before `timeout` elapsed.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_zone::OvhDnsZone;
+    /// use ovh::order::OvhPlacedOrder;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let order = OvhDnsZone::order_anycast(&c, "example.com", "P1Y")
+    ///         .await
+    ///         .unwrap();
+    ///     let id = order.order_id.unwrap();
+    ///     OvhPlacedOrder::pay(&c, id, 1234).await.unwrap();
+    ///
+    ///     let services = OvhPlacedOrder::wait_until_delivered(&c, id, Duration::from_secs(3600))
+    ///         .await
+    ///         .unwrap();
+    ///     println!("{:?}", services);
+    /// }
+    /// ```
+    pub async fn wait_until_delivered(
+        c: &OvhClient,
+        id: u64,
+        timeout: Duration,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match Self::status(c, id).await? {
+                OvhOrderStatus::Delivered => break,
+                OvhOrderStatus::Cancelling | OvhOrderStatus::Cancelled => {
+                    return Err(format!("order {} was cancelled", id).into())
+                }
+                _ => {}
+            }
+
+            if Instant::now() + POLL_INTERVAL > deadline {
+                return Err(format!("timed out waiting for delivery of order {}", id).into());
+            }
+            c.sleep(POLL_INTERVAL).await;
+        }
+
+        // Lines not tied to a service, such as fees, have a placeholder
+        // domain starting with `*`.
+        let mut services: Vec<String> = Vec::new();
+        for d in Self::details(c, id).await? {
+            if !d.domain.is_empty() && !d.domain.starts_with('*') && !services.contains(&d.domain) {
+                services.push(d.domain);
+            }
+        }
+        Ok(services)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct OvhCartCheckout {
+    #[serde(rename(serialize = "autoPayWithPreferredPaymentMethod"))]
+    auto_pay_with_preferred_payment_method: bool,
+    #[serde(rename(serialize = "waiveRetractationPeriod"))]
+    waive_retractation_period: bool,
+}
+
+/// Namespace for the operations on order carts.
+pub struct OvhCart;
+
+impl OvhCart {
+    /// Retrieves the price of the content of a cart, without ordering it.
+    pub async fn quote(
+        c: &OvhClient,
+        cart: &str,
+    ) -> Result<OvhOrder, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_json(&format!("/order/cart/{}/checkout", cart))
+            .await?;
+        Ok(res)
+    }
+
+    /// Orders the content of a cart.
+    ///
+    /// With `auto_pay`, the order is paid right away with the default
+    /// payment method of the account (see
+    /// [`OvhPaymentMethod::set_default`](crate::me::OvhPaymentMethod::set_default)),
+    /// so that no manual step is needed before its delivery. Otherwise the
+    /// order must be paid, for instance with [`OvhPlacedOrder::pay`].
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use ovh::client::OvhClient;
+    /// use ovh::order::{OvhCart, OvhPlacedOrder};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let order = OvhCart::checkout(&c, "cart-id", true).await.unwrap();
+    ///     let services = OvhPlacedOrder::wait_until_delivered(
+    ///         &c,
+    ///         order.order_id.unwrap(),
+    ///         Duration::from_secs(3600),
+    ///     )
+    ///     .await
+    ///     .unwrap();
+    ///     println!("{:?}", services);
+    /// }
+    /// ```
+    pub async fn checkout(
+        c: &OvhClient,
+        cart: &str,
+        auto_pay: bool,
+    ) -> Result<OvhOrder, Box<dyn std::error::Error + Send + Sync>> {
+        let data = OvhCartCheckout {
+            auto_pay_with_preferred_payment_method: auto_pay,
+            waive_retractation_period: false,
+        };
+        let res = c
+            .post_json(&format!("/order/cart/{}/checkout", cart), &data)
+            .await?;
+        Ok(res)
+    }
+}
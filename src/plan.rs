@@ -0,0 +1,140 @@
+//! Machine-readable plans of changes.
+//!
+//! Features computing changes before applying them, such as
+//! [`ZoneReconciler::plan`](crate::zone_reconciler::ZoneReconciler::plan)
+//! or [`OvhMailRedir::plan_import`](crate::email_redir::OvhMailRedir::plan_import),
+//! describe them as a [`Plan`]. Plans serialize to JSON, so CI systems can
+//! parse them, require approvals or post them as comments, and display as
+//! a short human-readable summary.
+
+use std::{fmt, iter::FromIterator};
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of a resource changed by a plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ResourceKind {
+    /// Records of a DNS zone sharing a subdomain and a type
+    DnsRecordSet,
+    /// Email redirection
+    EmailRedirection,
+}
+
+/// What a change does to its resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    /// The resource is created
+    Create,
+    /// The resource is modified
+    Update,
+    /// The resource is deleted
+    Delete,
+}
+
+/// Change to a single resource.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Change {
+    /// Kind of the resource
+    pub kind: ResourceKind,
+    /// Name of the resource, such as `example.com/www/A`
+    pub resource: String,
+    /// What is done to the resource
+    pub action: ChangeAction,
+    /// State of the resource before the change, `None` for a creation
+    pub before: Option<serde_json::Value>,
+    /// State of the resource after the change, `None` for a deletion
+    pub after: Option<serde_json::Value>,
+}
+
+/// Ordered list of changes.
+///
+/// ```
+/// use ovh::plan::{Change, ChangeAction, Plan, ResourceKind};
+///
+/// let plan: Plan = vec![Change {
+///     kind: ResourceKind::EmailRedirection,
+///     resource: "example.com/foo@example.com -> admin@example.com".into(),
+///     action: ChangeAction::Create,
+///     before: None,
+///     after: Some(serde_json::json!({"to": "admin@example.com"})),
+/// }]
+/// .into_iter()
+/// .collect();
+///
+/// assert_eq!(plan.count(ChangeAction::Create), 1);
+/// assert_eq!(
+///     plan.to_string(),
+///     "+ email_redirection example.com/foo@example.com -> admin@example.com\n\
+///      Plan: 1 to create, 0 to update, 0 to delete.\n"
+/// );
+/// let json = serde_json::to_string(&plan).unwrap();
+/// assert_eq!(serde_json::from_str::<Plan>(&json).unwrap(), plan);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Plan {
+    /// Changes, in the order they are applied
+    pub changes: Vec<Change>,
+}
+
+impl Plan {
+    /// Creates an empty plan.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the plan changes nothing.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the number of changes doing `action`.
+    pub fn count(&self, action: ChangeAction) -> usize {
+        self.changes.iter().filter(|c| c.action == action).count()
+    }
+}
+
+impl FromIterator<Change> for Plan {
+    fn from_iter<I: IntoIterator<Item = Change>>(iter: I) -> Self {
+        Self {
+            changes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ResourceKind::DnsRecordSet => "dns_record_set",
+            ResourceKind::EmailRedirection => "email_redirection",
+        })
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = match self.action {
+            ChangeAction::Create => '+',
+            ChangeAction::Update => '~',
+            ChangeAction::Delete => '-',
+        };
+        write!(f, "{} {} {}", sign, self.kind, self.resource)
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in &self.changes {
+            writeln!(f, "{}", c)?;
+        }
+        writeln!(
+            f,
+            "Plan: {} to create, {} to update, {} to delete.",
+            self.count(ChangeAction::Create),
+            self.count(ChangeAction::Update),
+            self.count(ChangeAction::Delete)
+        )
+    }
+}
@@ -0,0 +1,253 @@
+//! Policies restricting the mutating calls of a client.
+//!
+//! Policies are registered with
+//! [`OvhClient::with_policy`](crate::client::OvhClient::with_policy) and
+//! consulted before every POST, PUT, PATCH or DELETE request, dry-run ones
+//! included, which they can tell apart with [`PolicyRequest::dry_run`]. A
+//! request denied by any of them is not sent, and fails with
+//! [`OvhError::PolicyDenied`](crate::error::OvhError::PolicyDenied). They
+//! guard automation against mistakes, such as deleting records of a
+//! production zone, but are no substitute for the access rules of the
+//! consumer key.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use reqwest::Method;
+
+/// Mutating request submitted to a [`Policy`].
+#[derive(Debug, Clone, Copy)]
+pub struct PolicyRequest<'a> {
+    /// HTTP method
+    pub method: &'a Method,
+    /// Path of the route, without the API version nor the query string,
+    /// such as `/domain/zone/example.com/record/42`
+    pub path: &'a str,
+    /// JSON body, empty for requests without one
+    pub body: &'a str,
+    /// Whether the client is in dry-run mode, so that the request will not
+    /// actually be sent
+    pub dry_run: bool,
+}
+
+/// Rule deciding whether a mutating request may be sent.
+///
+/// Closures taking a [`PolicyRequest`] are policies:
+///
+/// ```
+/// use ovh::client::OvhClient;
+/// use ovh::policy::PolicyRequest;
+///
+/// let client = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key")
+///     .unwrap()
+///     .with_policy(|req: &PolicyRequest<'_>| {
+///         if req.path.starts_with("/me/") {
+///             return Err("account settings are managed by hand".to_string());
+///         }
+///         Ok(())
+///     });
+/// ```
+pub trait Policy: Send + Sync {
+    /// Checks whether a request may be sent, returning why it is denied
+    /// otherwise.
+    fn check(&self, req: &PolicyRequest<'_>) -> Result<(), String>;
+}
+
+impl<F> Policy for F
+where
+    F: Fn(&PolicyRequest<'_>) -> Result<(), String> + Send + Sync,
+{
+    fn check(&self, req: &PolicyRequest<'_>) -> Result<(), String> {
+        self(req)
+    }
+}
+
+/// Denies the requests whose path matches a pattern.
+///
+/// In patterns, `*` matches any sequence of characters, slashes included.
+/// Paths are compared case-insensitively.
+///
+/// ```
+/// use ovh::policy::{DenyRoute, Policy, PolicyRequest};
+/// use reqwest::Method;
+///
+/// let policy = DenyRoute::new("/domain/zone/*.prod.example.com/*").methods(&[Method::DELETE]);
+///
+/// let req = |method, path| PolicyRequest { method, path, body: "", dry_run: false };
+/// assert!(policy.check(&req(&Method::DELETE, "/domain/zone/api.prod.example.com/record/42")).is_err());
+/// assert!(policy.check(&req(&Method::POST, "/domain/zone/api.prod.example.com/record")).is_ok());
+/// assert!(policy.check(&req(&Method::DELETE, "/domain/zone/staging.example.com/record/42")).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DenyRoute {
+    pattern: String,
+    methods: Vec<Method>,
+}
+
+impl DenyRoute {
+    /// Creates a policy denying every mutating request whose path matches
+    /// `pattern`.
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.into(),
+            methods: Vec::new(),
+        }
+    }
+
+    /// Only denies the requests with one of these methods.
+    pub fn methods(mut self, methods: &[Method]) -> Self {
+        self.methods = methods.to_vec();
+        self
+    }
+}
+
+impl Policy for DenyRoute {
+    fn check(&self, req: &PolicyRequest<'_>) -> Result<(), String> {
+        let method_matches = self.methods.is_empty() || self.methods.contains(req.method);
+        if method_matches && glob_match(&self.pattern, req.path) {
+            return Err(format!(
+                "{} {} matches denied route `{}`",
+                req.method, req.path, self.pattern
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Whether `path` matches `pattern`, where `*` matches any sequence of
+/// characters.
+fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let path = path.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let mut rest = match path.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let (last, middle) = match parts.split_last() {
+        Some(split) => split,
+        None => return rest.is_empty(),
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+/// Denies the mutating requests during business hours, unless the client
+/// is in dry-run mode.
+///
+/// Changes are thus only applied outside of business hours, while they
+/// can still be previewed with
+/// [`OvhClient::with_dry_run`](crate::client::OvhClient::with_dry_run)
+/// at any time. Hours are given in a fixed offset from UTC, which does not
+/// follow daylight saving time.
+///
+/// ```
+/// use ovh::policy::{BusinessHours, Policy, PolicyRequest};
+/// use reqwest::Method;
+///
+/// // From 9:00 to 18:00 in UTC+2, Monday to Friday.
+/// let policy = BusinessHours::new().hours(9, 18).utc_offset_minutes(120);
+///
+/// // Thursday 1 January 1970, 10:00 in UTC+2
+/// assert!(policy.is_business_hours_at(8 * 3600));
+/// // Thursday 1 January 1970, 19:00 in UTC+2
+/// assert!(!policy.is_business_hours_at(17 * 3600));
+/// // Saturday 3 January 1970, 10:00 in UTC+2
+/// assert!(!policy.is_business_hours_at(2 * 86_400 + 8 * 3600));
+///
+/// // Dry runs are always allowed.
+/// let path = "/domain/zone/example.com/record/42";
+/// let req = PolicyRequest { method: &Method::DELETE, path, body: "", dry_run: true };
+/// assert!(policy.check(&req).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct BusinessHours {
+    start_hour: u32,
+    end_hour: u32,
+    utc_offset_minutes: i32,
+    weekends: bool,
+}
+
+impl Default for BusinessHours {
+    fn default() -> Self {
+        Self {
+            start_hour: 9,
+            end_hour: 18,
+            utc_offset_minutes: 0,
+            weekends: false,
+        }
+    }
+}
+
+impl BusinessHours {
+    /// Creates the default policy, denying changes from 9:00 to 18:00 UTC,
+    /// Monday to Friday.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the hour business hours start at, included, and the hour they
+    /// end at, excluded.
+    pub fn hours(mut self, start: u32, end: u32) -> Self {
+        self.start_hour = start;
+        self.end_hour = end;
+        self
+    }
+
+    /// Sets the offset from UTC of the hours, in minutes.
+    pub fn utc_offset_minutes(mut self, offset: i32) -> Self {
+        self.utc_offset_minutes = offset;
+        self
+    }
+
+    /// Whether Saturdays and Sundays also have business hours.
+    pub fn weekends(mut self, included: bool) -> Self {
+        self.weekends = included;
+        self
+    }
+
+    /// Whether a time, in seconds since the Unix epoch, is within business
+    /// hours.
+    pub fn is_business_hours_at(&self, timestamp: i64) -> bool {
+        let local = timestamp + i64::from(self.utc_offset_minutes) * 60;
+        let days = local.div_euclid(86_400);
+        let hour = local.rem_euclid(86_400) / 3600;
+        // 1 January 1970 was a Thursday, Monday being 0.
+        let weekday = (days + 3).rem_euclid(7);
+
+        (self.weekends || weekday < 5)
+            && hour >= i64::from(self.start_hour)
+            && hour < i64::from(self.end_hour)
+    }
+}
+
+impl Policy for BusinessHours {
+    fn check(&self, req: &PolicyRequest<'_>) -> Result<(), String> {
+        if req.dry_run {
+            return Ok(());
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        if self.is_business_hours_at(now) {
+            return Err(format!(
+                "changes are not allowed from {}:00 to {}:00{}, outside of dry runs",
+                self.start_hour,
+                self.end_hour,
+                if self.weekends {
+                    ""
+                } else {
+                    ", Monday to Friday"
+                }
+            ));
+        }
+        Ok(())
+    }
+}
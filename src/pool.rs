@@ -0,0 +1,154 @@
+//! Registry of clients for several OVH accounts.
+
+use std::{future::Future, path::Path};
+
+use crate::client::OvhClient;
+use configparser::ini::Ini;
+
+/// Named clients, one per OVH account.
+///
+/// ```no_run
+/// use ovh::pool::OvhClientPool;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let pool = OvhClientPool::from_conf("accounts.conf").unwrap();
+///     let res = pool
+///         .for_each_account(|_, c| async move { c.get_json::<Vec<String>>("/domain/zone").await })
+///         .await;
+///
+///     for (account, zones) in res {
+///         match zones {
+///             Ok(zones) => println!("{}: {} zones", account, zones.len()),
+///             Err(e) => println!("{}: {}", account, e),
+///         }
+///     }
+/// }
+/// ```
+#[derive(Default)]
+pub struct OvhClientPool {
+    clients: Vec<(String, OvhClient)>,
+}
+
+impl OvhClientPool {
+    /// Creates an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a client to the pool, replacing any client with the same name.
+    pub fn insert(&mut self, name: &str, client: OvhClient) {
+        match self.clients.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = client,
+            None => self.clients.push((name.into(), client)),
+        }
+    }
+
+    /// Creates a pool from a configuration file holding several accounts.
+    ///
+    /// Every section defining `application_key`, `application_secret` and
+    /// `consumer_key` is an account named after the section. Its endpoint
+    /// is the `endpoint` key of the section, or the section name itself if
+    /// it is an endpoint name, as in the files read by
+    /// [`OvhClient::from_conf`]:
+    ///
+    /// ```ini
+    /// [customer-a]
+    /// endpoint=ovh-eu
+    /// application_key=my_app_key
+    /// application_secret=my_application_secret
+    /// consumer_key=my_consumer_key
+    ///
+    /// [customer-b]
+    /// endpoint=ovh-ca
+    /// application_key=my_other_app_key
+    /// application_secret=my_other_application_secret
+    /// consumer_key=my_other_consumer_key
+    /// ```
+    pub fn from_conf<T>(path: T) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: AsRef<Path>,
+    {
+        let mut conf = Ini::new();
+        conf.load(path)?;
+
+        let mut pool = Self::new();
+        for section in conf.sections() {
+            let (ak, as_, ck) = match (
+                conf.get(&section, "application_key"),
+                conf.get(&section, "application_secret"),
+                conf.get(&section, "consumer_key"),
+            ) {
+                (Some(ak), Some(as_), Some(ck)) => (ak, as_, ck),
+                _ => continue,
+            };
+            let endpoint = conf
+                .get(&section, "endpoint")
+                .unwrap_or_else(|| section.clone());
+
+            let c = OvhClient::builder(&endpoint, &ak, &as_, &ck)
+                .build()
+                .map_err(|e| format!("account `{}`: {}", section, e))?;
+            pool.insert(&section, c);
+        }
+
+        Ok(pool)
+    }
+
+    /// Creates a pool from several configuration files, each one read by
+    /// [`OvhClient::from_conf`] and named after the stem of its file name.
+    pub fn from_confs<T>(paths: &[T]) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: AsRef<Path>,
+    {
+        let mut pool = Self::new();
+        for path in paths {
+            let path = path.as_ref();
+            let name = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .ok_or_else(|| format!("invalid file name {}", path.display()))?;
+            let c = OvhClient::from_conf(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            pool.insert(name, c);
+        }
+
+        Ok(pool)
+    }
+
+    /// Returns the client of an account.
+    pub fn get(&self, name: &str) -> Option<&OvhClient> {
+        self.clients.iter().find(|(n, _)| n == name).map(|(_, c)| c)
+    }
+
+    /// Iterates over the accounts, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &OvhClient)> {
+        self.clients.iter().map(|(n, c)| (n.as_str(), c))
+    }
+
+    /// Returns the number of accounts.
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Returns whether the pool holds no account.
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    /// Runs an operation on every account concurrently.
+    ///
+    /// Results are returned along with the name of their account, in
+    /// insertion order.
+    pub async fn for_each_account<'a, F, Fut, T>(&'a self, f: F) -> Vec<(String, T)>
+    where
+        F: Fn(&'a str, &'a OvhClient) -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let res = futures::future::join_all(self.iter().map(|(n, c)| f(n, c))).await;
+        self.clients
+            .iter()
+            .map(|(n, _)| n.clone())
+            .zip(res)
+            .collect()
+    }
+}
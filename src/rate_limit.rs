@@ -0,0 +1,110 @@
+//! Client-side rate limiting.
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::timer::Timer;
+
+/// Limits the rate and the concurrency of the requests sent by a client.
+///
+/// The rate is enforced with a token bucket allowing bursts of up to one
+/// second worth of requests. Requests exceeding the budget wait for their
+/// turn instead of failing.
+///
+/// ```
+/// use ovh::client::OvhClient;
+/// use ovh::rate_limit::RateLimiter;
+///
+/// let client = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key")
+///     .unwrap()
+///     .with_rate_limit(RateLimiter::new().per_second(10.0).max_concurrency(4));
+/// ```
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    bucket: Option<Mutex<TokenBucket>>,
+    semaphore: Option<Semaphore>,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64) -> Self {
+        let capacity = rate.max(1.0);
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            last: Instant::now(),
+        }
+    }
+
+    /// Takes a token, returning how long to wait before it is actually
+    /// available.
+    fn take(&mut self) -> Duration {
+        let now = Instant::now();
+        let refill = now.duration_since(self.last).as_secs_f64() * self.rate;
+        self.tokens = (self.tokens + refill).min(self.capacity);
+        self.last = now;
+
+        // The token is reserved even if the bucket is empty, so that
+        // concurrent callers queue up instead of all waking up at once.
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Creates a limiter that does not limit anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of requests started per second.
+    pub fn per_second(mut self, rate: f64) -> Self {
+        self.bucket = if rate > 0.0 {
+            Some(Mutex::new(TokenBucket::new(rate)))
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Limits the number of requests in flight at the same time.
+    pub fn max_concurrency(mut self, max: usize) -> Self {
+        self.semaphore = Some(Semaphore::new(max.max(1)));
+        self
+    }
+
+    /// Waits until a request can be sent.
+    ///
+    /// The returned permit must be kept while the request is in flight.
+    pub(crate) async fn acquire(&self, timer: &dyn Timer) -> Option<SemaphorePermit<'_>> {
+        let permit = match &self.semaphore {
+            Some(s) => s.acquire().await.ok(),
+            None => None,
+        };
+
+        if let Some(bucket) = &self.bucket {
+            let wait = bucket.lock().unwrap().take();
+            if !wait.is_zero() {
+                timer.sleep(wait).await;
+            }
+        }
+
+        permit
+    }
+}
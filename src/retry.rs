@@ -0,0 +1,118 @@
+//! Retry policy for idempotent requests.
+
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+/// Policy deciding how many times, and how long apart, idempotent requests
+/// failing with a transient error are attempted.
+///
+/// Delays grow exponentially from `initial_delay`, by a factor of
+/// `multiplier` per attempt, up to `max_delay`. With jitter enabled, the
+/// actual delay is picked at random between zero and that value, so that
+/// many clients throttled at the same time do not retry in lockstep.
+///
+/// ```
+/// use std::time::Duration;
+/// use ovh::retry::RetryPolicy;
+///
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .initial_delay(Duration::from_millis(100))
+///     .jitter(false);
+///
+/// assert_eq!(policy.delay(1), Duration::from_millis(100));
+/// assert_eq!(policy.delay(2), Duration::from_millis(200));
+/// assert_eq!(policy.delay(3), Duration::from_millis(400));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: usize,
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: u32,
+    jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2,
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates the default policy: 3 attempts, starting at 500ms and
+    /// doubling, with jitter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy that never retries.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Sets the total number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Sets the maximum delay between two attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Sets the factor applied to the delay after each attempt.
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Enables or disables jitter.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns the total number of attempts, including the first one.
+    pub fn attempts(&self) -> usize {
+        self.max_attempts
+    }
+
+    /// Returns the delay to wait after the given failed attempt, counting
+    /// from 1.
+    pub fn delay(&self, attempt: usize) -> Duration {
+        let exp = attempt.saturating_sub(1).min(u32::MAX as usize) as u32;
+        let factor = self.multiplier.saturating_pow(exp);
+        let delay = self
+            .initial_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+
+        if !self.jitter {
+            return delay;
+        }
+
+        // RandomState is seeded randomly, which is plenty for jitter and
+        // avoids depending on a random number generator.
+        let random = RandomState::new().build_hasher().finish();
+        delay.mul_f64(random as f64 / u64::MAX as f64)
+    }
+}
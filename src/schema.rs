@@ -0,0 +1,574 @@
+//! Introspection of the API schemas and generation of Rust bindings.
+//!
+//! Every section of the API, such as `/domain` or `/cloud`, publishes a
+//! schema describing its routes and models. [`OvhApiSchema`] retrieves and
+//! deserializes it, and [`CodeGenerator`] turns it into a Rust module
+//! holding one struct or enum per model and one function per operation,
+//! written in the style of the hand-written modules of this crate.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+};
+
+use serde::Deserialize;
+
+use crate::client::OvhClient;
+
+/// Schema of a section of the API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhApiSchema {
+    /// Version of the API, such as `1.0`
+    #[serde(rename(deserialize = "apiVersion"))]
+    pub api_version: String,
+    /// Root of the section, such as `/domain`
+    #[serde(rename(deserialize = "resourcePath"))]
+    pub resource_path: String,
+    /// Routes of the section
+    pub apis: Vec<OvhApiPath>,
+    /// Models used by the routes, keyed by their full name
+    pub models: BTreeMap<String, OvhApiModel>,
+}
+
+/// A route of the API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhApiPath {
+    /// Path of the route, with its parameters between braces, such as
+    /// `/domain/zone/{zoneName}`
+    pub path: String,
+    /// Description of the route
+    #[serde(default)]
+    pub description: String,
+    /// Operations available on the route
+    pub operations: Vec<OvhApiOperation>,
+}
+
+/// An operation of a route, one per HTTP method.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhApiOperation {
+    /// HTTP method of the operation
+    #[serde(rename(deserialize = "httpMethod"))]
+    pub http_method: String,
+    /// Description of the operation
+    #[serde(default)]
+    pub description: String,
+    /// Type of the response, `void` if there is none
+    #[serde(rename(deserialize = "responseType"))]
+    pub response_type: String,
+    /// Parameters of the operation
+    #[serde(default)]
+    pub parameters: Vec<OvhApiParameter>,
+    /// Lifecycle of the operation
+    #[serde(rename(deserialize = "apiStatus"))]
+    pub api_status: Option<OvhApiStatus>,
+    /// Whether the operation can be called without credentials
+    #[serde(rename(deserialize = "noAuthentication"), default)]
+    pub no_authentication: bool,
+}
+
+/// A parameter of an operation.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhApiParameter {
+    /// Name of the parameter, `null` for a body made of a whole model
+    pub name: Option<String>,
+    /// Where the parameter goes: `path`, `query` or `body`
+    #[serde(rename(deserialize = "paramType"))]
+    pub param_type: String,
+    /// Type of the parameter
+    #[serde(rename(deserialize = "dataType"))]
+    pub data_type: String,
+    /// Whether the parameter is mandatory
+    #[serde(default)]
+    pub required: bool,
+    /// Description of the parameter
+    pub description: Option<String>,
+}
+
+/// Lifecycle of an operation.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhApiStatus {
+    /// Status of the operation: `PRODUCTION`, `BETA`, `ALPHA`,
+    /// `DEPRECATED` or `DELETED`
+    pub value: String,
+}
+
+/// A model of the API, either an object or an enumeration.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhApiModel {
+    /// Short name of the model, such as `Zone`
+    pub id: String,
+    /// Namespace of the model, such as `domain.zone`
+    pub namespace: String,
+    /// Description of the model
+    pub description: Option<String>,
+    /// Type parameters of a generic model, such as `T` for
+    /// `complexType.UnitAndValue<T>`
+    #[serde(default)]
+    pub generics: Vec<String>,
+    /// Values of an enumeration
+    #[serde(rename(deserialize = "enum"))]
+    pub enum_values: Option<Vec<String>>,
+    /// Properties of an object, keyed by name
+    #[serde(default)]
+    pub properties: BTreeMap<String, OvhApiProperty>,
+}
+
+/// A property of an object model.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhApiProperty {
+    /// Type of the property
+    #[serde(rename(deserialize = "type"))]
+    pub property_type: String,
+    /// Whether the property may be `null`
+    #[serde(rename(deserialize = "canBeNull"), default)]
+    pub can_be_null: bool,
+    /// Description of the property
+    pub description: Option<String>,
+}
+
+impl OvhApiSchema {
+    /// Retrieves the schema of a section of the API, such as `/domain`.
+    ///
+    /// Schemas are public, so the request is not signed.
+    pub async fn get(
+        c: &OvhClient,
+        section: &str,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let resp = c
+            .get_noauth(&format!("/{}.json", section.trim_matches('/')))
+            .await?;
+        c.read_json(resp).await
+    }
+}
+
+/// Generator of a Rust module from an API schema.
+///
+/// Models become structs, or enums for enumerations, named after their
+/// full name with the `Ovh` prefix: `domain.zone.Record` becomes
+/// `OvhDomainZoneRecord`, and generic models keep their type parameters.
+/// Operations become async functions named after their method and path:
+/// `GET /domain/zone/{zoneName}` becomes `get_domain_zone_zone_name`,
+/// taking the client and the path parameters, followed by the query
+/// parameters, if any, and the body for POST and PUT operations. Deleted
+/// operations are skipped and deprecated ones are marked as such.
+///
+/// ```
+/// use ovh::schema::{CodeGenerator, OvhApiSchema};
+///
+/// let schema: OvhApiSchema = serde_json::from_str(r#"{
+///     "apiVersion": "1.0",
+///     "resourcePath": "/example",
+///     "apis": [{
+///         "path": "/example/{serviceName}/task",
+///         "operations": [{
+///             "httpMethod": "DELETE",
+///             "responseType": "void",
+///             "parameters": [
+///                 {"name": "serviceName", "paramType": "path", "dataType": "string", "required": true},
+///                 {"name": "force", "paramType": "query", "dataType": "boolean"}
+///             ]
+///         }]
+///     }],
+///     "models": {
+///         "complexType.UnitAndValue<T>": {
+///             "id": "UnitAndValue",
+///             "namespace": "complexType",
+///             "generics": ["T"],
+///             "properties": {
+///                 "unit": {"type": "string"},
+///                 "value": {"type": "T"}
+///             }
+///         },
+///         "example.Quota": {
+///             "id": "Quota",
+///             "namespace": "example",
+///             "properties": {"size": {"type": "complexType.UnitAndValue<long>"}}
+///         },
+///         "example.State": {
+///             "id": "State",
+///             "namespace": "example",
+///             "enum": ["in-progress", "inProgress", "Unknown"]
+///         }
+///     }
+/// }"#).unwrap();
+///
+/// let code = CodeGenerator::new().generate(&schema);
+/// assert!(code.contains("pub struct OvhComplexTypeUnitAndValue<T> {"));
+/// assert!(code.contains("    pub value: T,"));
+/// assert!(code.contains("    pub size: OvhComplexTypeUnitAndValue<i64>,"));
+/// assert!(code.contains("    InProgress,\n"));
+/// assert!(code.contains("    InProgress2,\n"));
+/// assert!(code.contains("    Unknown2,\n"));
+/// assert!(code.contains("    #[serde(other, rename = \"Unknown_\")]\n    Unknown,\n"));
+/// assert!(code.contains(
+///     "pub async fn delete_example_service_name_task(c: &OvhClient, service_name: &str, \
+///      params: &[(&str, &str)])"
+/// ));
+/// assert!(code.contains("c.delete(&ovh::client::path_with_params("));
+/// ```
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::schema::{CodeGenerator, OvhApiSchema};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let schema = OvhApiSchema::get(&c, "/domain").await.unwrap();
+///     let code = CodeGenerator::new()
+///         .with_prefix("/domain/zone")
+///         .generate(&schema);
+///     std::fs::write("src/generated_zone.rs", code).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CodeGenerator {
+    prefixes: Vec<String>,
+}
+
+impl CodeGenerator {
+    /// Creates a generator emitting every route of the schema.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the generated functions to the routes under `prefix`.
+    ///
+    /// May be called several times to select several subsections.
+    pub fn with_prefix(mut self, prefix: &str) -> Self {
+        self.prefixes.push(prefix.trim_end_matches('/').into());
+        self
+    }
+
+    fn selects(&self, path: &str) -> bool {
+        self.prefixes.is_empty()
+            || self.prefixes.iter().any(|p| {
+                path == p
+                    || path
+                        .strip_prefix(p.as_str())
+                        .is_some_and(|r| r.starts_with('/'))
+            })
+    }
+
+    /// Generates the source of a module for the schema.
+    pub fn generate(&self, schema: &OvhApiSchema) -> String {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "//! Bindings of the `{}` section of the API, generated from its schema.",
+            schema.resource_path
+        );
+        let _ = writeln!(out);
+        let _ = writeln!(out, "#![allow(clippy::all, dead_code)]");
+        let _ = writeln!(out);
+        let _ = writeln!(out, "use ovh::client::OvhClient;");
+        let _ = writeln!(out);
+        let _ = writeln!(out, "use serde::{{Deserialize, Serialize}};");
+
+        for (name, model) in &schema.models {
+            let _ = writeln!(out);
+            write_model(&mut out, name, model);
+        }
+
+        for api in schema.apis.iter().filter(|a| self.selects(&a.path)) {
+            for op in &api.operations {
+                if op.api_status.as_ref().is_some_and(|s| s.value == "DELETED") {
+                    continue;
+                }
+                let _ = writeln!(out);
+                write_operation(&mut out, &api.path, op);
+            }
+        }
+
+        out
+    }
+}
+
+fn write_doc(out: &mut String, indent: &str, doc: Option<&str>) {
+    if let Some(doc) = doc.map(str::trim).filter(|d| !d.is_empty()) {
+        for line in doc.lines() {
+            let _ = writeln!(out, "{}/// {}", indent, line.trim_end());
+        }
+    }
+}
+
+fn write_model(out: &mut String, name: &str, model: &OvhApiModel) {
+    write_doc(out, "", model.description.as_deref());
+    let type_name = model_name(name);
+
+    if let Some(values) = &model.enum_values {
+        let _ = writeln!(
+            out,
+            "#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]"
+        );
+        let _ = writeln!(out, "pub enum {} {{", type_name);
+        // `Unknown` is kept for the values added after the generation.
+        let mut used: HashSet<String> = HashSet::new();
+        used.insert("Unknown".into());
+        for v in values {
+            let _ = writeln!(out, "    #[serde(rename = \"{}\")]", v);
+            let _ = writeln!(out, "    {},", unique_name(&mut used, pascal_case(v)));
+        }
+        // The catch-all variant is (de)serialized under its own name, which
+        // must not be a value of the enumeration.
+        let mut other = String::from("Unknown");
+        while values.contains(&other) {
+            other.push('_');
+        }
+        if other == "Unknown" {
+            let _ = writeln!(out, "    #[serde(other)]");
+        } else {
+            let _ = writeln!(out, "    #[serde(other, rename = \"{}\")]", other);
+        }
+        let _ = writeln!(out, "    Unknown,");
+        let _ = writeln!(out, "}}");
+        return;
+    }
+
+    let _ = writeln!(out, "#[derive(Debug, Clone, Deserialize, Serialize)]");
+    if model.generics.is_empty() {
+        let _ = writeln!(out, "pub struct {} {{", type_name);
+    } else {
+        let _ = writeln!(
+            out,
+            "pub struct {}<{}> {{",
+            type_name,
+            model.generics.join(", ")
+        );
+    }
+    let mut used = HashSet::new();
+    for (prop, p) in &model.properties {
+        write_doc(out, "    ", p.description.as_deref());
+        let field = unique_name(&mut used, snake_case(prop));
+        if field != *prop {
+            let _ = writeln!(out, "    #[serde(rename = \"{}\")]", prop);
+        }
+        let ty = rust_type(&p.property_type, &model.namespace, &model.generics);
+        if p.can_be_null {
+            let _ = writeln!(out, "    pub {}: Option<{}>,", field, ty);
+        } else {
+            let _ = writeln!(out, "    pub {}: {},", field, ty);
+        }
+    }
+    let _ = writeln!(out, "}}");
+}
+
+/// Returns `name`, followed by a number if it is already used.
+fn unique_name(used: &mut HashSet<String>, name: String) -> String {
+    let mut res = name.clone();
+    let mut n = 2;
+    while !used.insert(res.clone()) {
+        res = format!("{}{}", name, n);
+        n += 1;
+    }
+    res
+}
+
+fn write_operation(out: &mut String, path: &str, op: &OvhApiOperation) {
+    let method = op.http_method.to_ascii_lowercase();
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| snake_case(s.trim_start_matches('{').trim_end_matches('}')))
+        .collect();
+    let fn_name = format!("{}_{}", method, segments.join("_"));
+
+    let mut args = vec!["c: &OvhClient".to_string()];
+    let mut format_path = String::new();
+    let mut format_args = Vec::new();
+    for s in path.split('/').filter(|s| !s.is_empty()) {
+        format_path.push('/');
+        if s.starts_with('{') {
+            let arg = snake_case(s.trim_start_matches('{').trim_end_matches('}'));
+            format_path.push_str("{}");
+            args.push(format!("{}: &str", arg));
+            format_args.push(arg);
+        } else {
+            format_path.push_str(s);
+        }
+    }
+
+    let has_query = op.parameters.iter().any(|p| p.param_type == "query");
+    let has_body = op.parameters.iter().any(|p| p.param_type == "body");
+    if has_query {
+        args.push("params: &[(&str, &str)]".into());
+    }
+    let with_body = (method == "post" || method == "put") && has_body;
+    if with_body {
+        args.push("body: &B".into());
+    }
+
+    let void = op.response_type == "void";
+    let ret = if void {
+        "()".to_string()
+    } else {
+        rust_type(&op.response_type, "", &[])
+    };
+
+    write_doc(out, "", Some(&op.description));
+    if op
+        .api_status
+        .as_ref()
+        .is_some_and(|s| s.value == "DEPRECATED")
+    {
+        let _ = writeln!(out, "#[deprecated]");
+    }
+    let generics = if with_body {
+        "<B: Serialize + ?Sized>"
+    } else {
+        ""
+    };
+    let _ = writeln!(
+        out,
+        "pub async fn {}{}({}) -> Result<{}, Box<dyn std::error::Error + Send + Sync>> {{",
+        fn_name,
+        generics,
+        args.join(", "),
+        ret
+    );
+
+    let mut path_expr = if format_args.is_empty() {
+        format!("\"{}\"", format_path)
+    } else {
+        format!("&format!(\"{}\", {})", format_path, format_args.join(", "))
+    };
+    if has_query && method != "get" {
+        path_expr = format!("&ovh::client::path_with_params({}, params)", path_expr);
+    }
+    let call = match method.as_str() {
+        "get" if has_query => format!("c.get_with_params({}, params)", path_expr),
+        "get" | "delete" => format!("c.{}({})", method, path_expr),
+        m if with_body => format!("c.{}({}, body)", m, path_expr),
+        m => format!("c.{}_empty({})", m, path_expr),
+    };
+    let _ = writeln!(out, "    let resp = {}.await?;", call);
+    if void {
+        let _ = writeln!(out, "    OvhClient::ensure_success(resp).await?;");
+        let _ = writeln!(out, "    Ok(())");
+    } else {
+        let _ = writeln!(out, "    c.read_json(resp).await");
+    }
+    let _ = writeln!(out, "}}");
+}
+
+/// Maps a type of the schema to a Rust type.
+///
+/// Unqualified model names are looked up in `namespace`, the namespace of
+/// the model using them, unless they are one of its `generics`.
+fn rust_type(ty: &str, namespace: &str, generics: &[String]) -> String {
+    if generics.iter().any(|g| g == ty) {
+        return ty.into();
+    }
+    if let Some(inner) = ty.strip_suffix("[]") {
+        return format!("Vec<{}>", rust_type(inner, namespace, generics));
+    }
+    if let Some(inner) = ty.strip_prefix("map[string]") {
+        return format!(
+            "std::collections::HashMap<String, {}>",
+            rust_type(inner, namespace, generics)
+        );
+    }
+    if let Some((base, args)) = ty.strip_suffix('>').and_then(|t| t.split_once('<')) {
+        let args: Vec<_> = args
+            .split(',')
+            .map(|a| rust_type(a.trim(), namespace, generics))
+            .collect();
+        return format!(
+            "{}<{}>",
+            rust_type(base, namespace, generics),
+            args.join(", ")
+        );
+    }
+    match ty {
+        "boolean" => "bool".into(),
+        "long" => "i64".into(),
+        "double" => "f64".into(),
+        "string"
+        | "text"
+        | "password"
+        | "date"
+        | "datetime"
+        | "time"
+        | "ip"
+        | "ipBlock"
+        | "ipv4"
+        | "ipv4Block"
+        | "ipv6"
+        | "ipv6Block"
+        | "ipInterface"
+        | "ipv4Interface"
+        | "ipv6Interface"
+        | "macAddress"
+        | "phoneNumber"
+        | "internationalPhoneNumber"
+        | "duration"
+        | "uuid"
+        | "coreTypes.AccountId:string" => "String".into(),
+        _ if ty.contains('.') => model_name(ty),
+        _ if !namespace.is_empty() && ty.starts_with(char::is_uppercase) => {
+            model_name(&format!("{}.{}", namespace, ty))
+        }
+        _ => "serde_json::Value".into(),
+    }
+}
+
+/// Returns the name of the Rust type of a model, such as
+/// `OvhDomainZoneRecord` for `domain.zone.Record`.
+fn model_name(name: &str) -> String {
+    let name = name.split('<').next().unwrap_or(name);
+    let mut res = String::from("Ovh");
+    for part in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        res.push_str(&pascal_case(part));
+    }
+    res
+}
+
+fn pascal_case(s: &str) -> String {
+    let mut res = String::new();
+    let mut upper = true;
+    for c in s.chars() {
+        if !c.is_ascii_alphanumeric() {
+            upper = true;
+        } else if upper {
+            res.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            res.push(c);
+        }
+    }
+    if res.starts_with(|c: char| c.is_ascii_digit()) || res.is_empty() {
+        res.insert(0, 'V');
+    }
+    res
+}
+
+fn snake_case(s: &str) -> String {
+    let mut res = String::new();
+    let mut prev_lower = false;
+    for c in s.chars() {
+        if c.is_ascii_uppercase() {
+            if prev_lower {
+                res.push('_');
+            }
+            res.push(c.to_ascii_lowercase());
+            prev_lower = false;
+        } else if c.is_ascii_alphanumeric() {
+            res.push(c);
+            prev_lower = true;
+        } else {
+            if !res.ends_with('_') {
+                res.push('_');
+            }
+            prev_lower = false;
+        }
+    }
+    let res = res.trim_matches('_').to_string();
+    match res.as_str() {
+        "type" | "match" | "ref" | "self" | "use" | "mod" | "fn" | "impl" | "loop" | "move"
+        | "static" | "struct" | "trait" | "where" | "as" | "in" | "enum" | "const" => {
+            format!("r#{}", res)
+        }
+        _ if res.starts_with(|c: char| c.is_ascii_digit()) => format!("_{}", res),
+        _ => res,
+    }
+}
@@ -0,0 +1,155 @@
+//! Pluggable sources for API credentials.
+//!
+//! A [`SecretProvider`] maps the keys used in `ovh.conf` (`endpoint`,
+//! `application_key`, `application_secret` and `consumer_key`) to their
+//! values, and can be handed to
+//! [`OvhClient::from_provider`](crate::client::OvhClient::from_provider)
+//! to build a client without a plaintext configuration file.
+
+use std::{collections::HashMap, path::PathBuf, process::Command};
+
+/// Source of credential values.
+pub trait SecretProvider {
+    /// Retrieves the value of a key, or `None` if the provider does not
+    /// define it.
+    fn get(&self, key: &str) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Retrieves the value of a key, failing if the provider does not
+    /// define it.
+    fn require(&self, key: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        self.get(key)?
+            .ok_or_else(|| format!("missing key `{}`", key).into())
+    }
+}
+
+/// Secrets already loaded in memory, for instance fetched from a vault.
+///
+/// ```
+/// use std::collections::HashMap;
+/// use ovh::secret::SecretProvider;
+///
+/// let mut secrets = HashMap::new();
+/// secrets.insert("endpoint".to_string(), "ovh-eu".to_string());
+///
+/// assert_eq!(secrets.require("endpoint").unwrap(), "ovh-eu");
+/// assert!(secrets.require("consumer_key").is_err());
+/// ```
+impl SecretProvider for HashMap<String, String> {
+    fn get(&self, key: &str) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(HashMap::get(self, key).cloned())
+    }
+}
+
+/// Secrets read from `OVH_*` environment variables.
+///
+/// Keys are upper-cased and prefixed, so `application_key` is read from
+/// `OVH_APPLICATION_KEY`, like python-ovh does.
+#[derive(Debug, Default)]
+pub struct EnvProvider;
+
+impl SecretProvider for EnvProvider {
+    fn get(&self, key: &str) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let var = format!("OVH_{}", key.to_uppercase());
+        match std::env::var(var) {
+            Ok(v) => Ok(Some(v)),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Secrets read from a [SOPS](https://github.com/getsops/sops)-encrypted
+/// file.
+///
+/// The file is decrypted once, by the `sops` binary found in `PATH`, and
+/// must contain a flat mapping of keys to string values.
+#[derive(Debug)]
+pub struct SopsProvider {
+    secrets: HashMap<String, String>,
+}
+
+impl SopsProvider {
+    /// Decrypts a SOPS file.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::secret::SopsProvider;
+    ///
+    /// let secrets = SopsProvider::load("ovh.enc.yaml").unwrap();
+    /// let client = OvhClient::from_provider(&secrets).unwrap();
+    /// ```
+    pub fn load<T>(path: T) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>
+    where
+        T: Into<PathBuf>,
+    {
+        let output = Command::new("sops")
+            .arg("--decrypt")
+            .arg("--output-type")
+            .arg("json")
+            .arg(path.into())
+            .output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("sops failed: {}", stderr.trim()).into());
+        }
+
+        let secrets = serde_json::from_slice(&output.stdout)?;
+        Ok(Self { secrets })
+    }
+}
+
+impl SecretProvider for SopsProvider {
+    fn get(&self, key: &str) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        SecretProvider::get(&self.secrets, key)
+    }
+}
+
+/// Secrets read from the secret store of the operating system, such as the
+/// Secret Service on Linux, the Keychain on macOS or the Credential Manager
+/// on Windows.
+///
+/// Every key is stored as the password of an entry of the given service,
+/// whose user name is the key, such as `application_secret`.
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::secret::KeyringProvider;
+///
+/// let client = OvhClient::from_provider(&KeyringProvider::new("ovh-billing")).unwrap();
+/// ```
+#[cfg(feature = "keyring")]
+#[derive(Debug, Clone)]
+pub struct KeyringProvider {
+    service: String,
+}
+
+#[cfg(feature = "keyring")]
+impl KeyringProvider {
+    /// Creates a provider reading the entries of a service.
+    pub fn new(service: &str) -> Self {
+        Self {
+            service: service.into(),
+        }
+    }
+
+    /// Stores the value of a key in the secret store.
+    pub fn set(
+        &self,
+        key: &str,
+        value: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        keyring::Entry::new(&self.service, key)?.set_password(value)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "keyring")]
+impl SecretProvider for KeyringProvider {
+    fn get(&self, key: &str) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        match keyring::Entry::new(&self.service, key)?.get_password() {
+            Ok(v) => Ok(Some(v)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,455 @@
+//! Operations shared by every kind of service.
+//!
+//! Services are designated by the path of their API route, such as
+//! `/domain/example.com` or `/dedicated/server/ns1234.ip-1-2-3.eu`.
+
+use crate::client::OvhClient;
+
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+/// Number of services processed concurrently by bulk operations.
+const BULK_CONCURRENCY: usize = 8;
+
+/// Structure representing a service of the account, whatever its kind.
+#[derive(Debug, Deserialize)]
+pub struct OvhService {
+    /// Identifier of the service in the billing system
+    #[serde(rename(deserialize = "serviceId"))]
+    pub service_id: u64,
+    /// API route of the service, if it has one
+    pub route: Option<OvhServiceRoute>,
+    /// Resource delivered by the service, with an empty name if the
+    /// endpoint does not tell it
+    #[serde(default)]
+    pub resource: OvhServiceResource,
+}
+
+/// Structure representing the API route of a service.
+#[derive(Debug, Deserialize)]
+pub struct OvhServiceRoute {
+    /// Route pattern, such as `/domain/{serviceName}`
+    pub path: Option<String>,
+    /// Path of the service, such as `/domain/example.com`
+    pub url: Option<String>,
+}
+
+/// Structure representing the resource delivered by a service.
+#[derive(Debug, Default, Deserialize)]
+pub struct OvhServiceResource {
+    /// Name of the resource
+    pub name: String,
+    /// Human-readable name of the resource
+    #[serde(rename(deserialize = "displayName"))]
+    pub display_name: Option<String>,
+}
+
+impl OvhService {
+    /// Retrieves a service.
+    pub async fn get(
+        c: &OvhClient,
+        id: u64,
+    ) -> Result<OvhService, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/services/{}", id)).await?;
+        Ok(res)
+    }
+
+    /// Lists all of the services of the account.
+    ///
+    /// This method will perform one extra API call per service
+    /// in order to get their details.
+    pub async fn list(
+        c: &OvhClient,
+    ) -> Result<Vec<OvhService>, Box<dyn std::error::Error + Send + Sync>> {
+        let ids = c.get_json::<Vec<u64>>("/services").await?;
+        let res: Vec<_> = futures::stream::iter(ids.iter().map(|&id| Self::get(c, id)))
+            .buffered(BULK_CONCURRENCY)
+            .collect()
+            .await;
+
+        let res = res.into_iter().filter_map(|c| c.ok()).collect();
+
+        Ok(res)
+    }
+
+    /// Path of the service in the API, such as `/domain/example.com`.
+    pub fn path(&self) -> Option<&str> {
+        self.route.as_ref()?.url.as_deref()
+    }
+}
+
+/// Structure representing the administrative information of a service.
+#[derive(Debug, Deserialize)]
+pub struct OvhServiceInfos {
+    /// Name of the service
+    pub domain: String,
+    /// Identifier of the service in the billing system
+    #[serde(rename(deserialize = "serviceId"))]
+    pub service_id: u64,
+    /// Current status of the service (`ok`, `expired`...), empty if the
+    /// endpoint does not tell it
+    #[serde(default)]
+    pub status: String,
+    /// Expiration date of the service, empty if the endpoint does not tell
+    /// it
+    #[serde(default)]
+    pub expiration: String,
+    /// Administrative contact (NIC handle), empty if the endpoint does not tell
+    /// it
+    #[serde(rename(deserialize = "contactAdmin"), default)]
+    pub contact_admin: String,
+    /// Technical contact (NIC handle), empty if the endpoint does not tell
+    /// it
+    #[serde(rename(deserialize = "contactTech"), default)]
+    pub contact_tech: String,
+    /// Billing contact (NIC handle), empty if the endpoint does not tell
+    /// it
+    #[serde(rename(deserialize = "contactBilling"), default)]
+    pub contact_billing: String,
+    /// Renewal settings of the service, if it is renewed
+    #[serde(default)]
+    pub renew: Option<OvhServiceRenew>,
+}
+
+/// Structure representing the renewal settings of a service.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OvhServiceRenew {
+    /// Whether the service is renewed automatically
+    #[serde(default)]
+    pub automatic: bool,
+    /// Whether the service is deleted when it expires
+    #[serde(rename(deserialize = "deleteAtExpiration"), default)]
+    pub delete_at_expiration: bool,
+    /// Whether the renewal mode is imposed by OVH
+    #[serde(default)]
+    pub forced: bool,
+    /// Whether automatic renewals wait for a manual payment
+    #[serde(rename(deserialize = "manualPayment"), default)]
+    pub manual_payment: Option<bool>,
+    /// Renewal period, in months
+    pub period: Option<u64>,
+}
+
+/// Contacts to give a service to. Contacts left to `None` are unchanged.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct OvhContactChange {
+    /// New administrative contact (NIC handle)
+    #[serde(
+        rename(serialize = "contactAdmin"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub contact_admin: Option<String>,
+    /// New technical contact (NIC handle)
+    #[serde(
+        rename(serialize = "contactTech"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub contact_tech: Option<String>,
+    /// New billing contact (NIC handle)
+    #[serde(
+        rename(serialize = "contactBilling"),
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub contact_billing: Option<String>,
+}
+
+impl OvhServiceInfos {
+    /// Retrieves the administrative information of a service.
+    pub async fn get(
+        c: &OvhClient,
+        service: &str,
+    ) -> Result<OvhServiceInfos, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("{}/serviceInfos", service)).await?;
+        Ok(res)
+    }
+
+    /// Requests a change of the contacts of a service.
+    ///
+    /// Returns the identifiers of the created
+    /// [`OvhContactChangeTask`](crate::me::OvhContactChangeTask)s, which
+    /// must be accepted with the tokens sent by email to the accounts
+    /// involved.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::me::OvhContactChangeTask;
+    /// use ovh::service::{OvhContactChange, OvhServiceInfos};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let change = OvhContactChange {
+    ///         contact_tech: Some("xx1234-ovh".into()),
+    ///         ..Default::default()
+    ///     };
+    ///     let tasks = OvhServiceInfos::change_contact(&c, "/domain/example.com", &change)
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     // Later, with the token received by email:
+    ///     OvhContactChangeTask::accept(&c, tasks[0], "token").await.unwrap();
+    /// }
+    /// ```
+    pub async fn change_contact(
+        c: &OvhClient,
+        service: &str,
+        change: &OvhContactChange,
+    ) -> Result<Vec<u64>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .post_json(&format!("{}/changeContact", service), change)
+            .await?;
+        Ok(res)
+    }
+}
+
+/// Outcome of the reassignment of the contacts of a service.
+#[derive(Debug)]
+pub struct OvhContactReassignment {
+    /// Path of the service
+    pub service: String,
+    /// What happened to the service
+    pub status: OvhContactReassignmentStatus,
+}
+
+/// What happened to a service during a contact reassignment.
+#[derive(Debug)]
+pub enum OvhContactReassignmentStatus {
+    /// The service already had the requested contacts
+    Unchanged,
+    /// Changes were requested, awaiting confirmation through these
+    /// [`OvhContactChangeTask`](crate::me::OvhContactChangeTask)s
+    Requested(Vec<u64>),
+    /// The change could not be requested
+    Failed(String),
+}
+
+/// Requests a change of contacts on every service matching a filter.
+///
+/// Services are selected by `filter`, given their path and current
+/// administrative information. Only the contacts that differ from the
+/// requested ones are changed, and contacts left to `None` are kept.
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::service::{reassign_contacts, OvhContactReassignmentStatus};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let report = reassign_contacts(
+///         &c,
+///         |path, infos| path.starts_with("/domain/") && infos.contact_tech == "old1234-ovh",
+///         None,
+///         Some("new1234-ovh"),
+///         None,
+///     )
+///     .await
+///     .unwrap();
+///
+///     for r in report {
+///         if let OvhContactReassignmentStatus::Requested(tasks) = r.status {
+///             println!("{}: tasks {:?}", r.service, tasks);
+///         }
+///     }
+/// }
+/// ```
+pub async fn reassign_contacts<F>(
+    c: &OvhClient,
+    filter: F,
+    new_admin: Option<&str>,
+    new_tech: Option<&str>,
+    new_billing: Option<&str>,
+) -> Result<Vec<OvhContactReassignment>, Box<dyn std::error::Error + Send + Sync>>
+where
+    F: Fn(&str, &OvhServiceInfos) -> bool,
+{
+    let services = OvhService::list(c).await?;
+    let paths: Vec<String> = services
+        .iter()
+        .filter_map(|s| s.path())
+        .map(String::from)
+        .collect();
+
+    let infos: Vec<_> = futures::stream::iter(paths.iter().map(|p| OvhServiceInfos::get(c, p)))
+        .buffered(BULK_CONCURRENCY)
+        .collect()
+        .await;
+
+    let wanted = |new: Option<&str>, current: &str| match new {
+        Some(n) if n != current => Some(n.to_string()),
+        _ => None,
+    };
+
+    let mut report = Vec::new();
+    for (path, infos) in paths.into_iter().zip(infos) {
+        // Services without contacts, such as sub-services, are skipped.
+        let infos = match infos {
+            Ok(i) => i,
+            Err(_) => continue,
+        };
+        if !filter(&path, &infos) {
+            continue;
+        }
+
+        let change = OvhContactChange {
+            contact_admin: wanted(new_admin, &infos.contact_admin),
+            contact_tech: wanted(new_tech, &infos.contact_tech),
+            contact_billing: wanted(new_billing, &infos.contact_billing),
+        };
+        let status = if change.contact_admin.is_none()
+            && change.contact_tech.is_none()
+            && change.contact_billing.is_none()
+        {
+            OvhContactReassignmentStatus::Unchanged
+        } else {
+            match OvhServiceInfos::change_contact(c, &path, &change).await {
+                Ok(tasks) => OvhContactReassignmentStatus::Requested(tasks),
+                Err(e) => OvhContactReassignmentStatus::Failed(e.to_string()),
+            }
+        };
+
+        report.push(OvhContactReassignment {
+            service: path,
+            status,
+        });
+    }
+
+    Ok(report)
+}
+
+/// Reason given when terminating a service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum OvhTerminationReason {
+    /// The features of the service do not fit
+    FeaturesDontSuitMe,
+    /// The service is not fast enough
+    LackOfPerformances,
+    /// The service is replaced by another OVH product
+    MigratedToAnotherOvhProduct,
+    /// The service is replaced by a competitor's product
+    MigratedToCompetitor,
+    /// The service is not needed anymore
+    NotNeededAnymore,
+    /// The service is not reliable enough
+    NotReliable,
+    /// The size of the service does not fit
+    ProductDimensionDontSuitMe,
+    /// The tools of the service do not fit
+    ProductToolsDontSuitMe,
+    /// The service is too expensive
+    TooExpensive,
+    /// The service is too hard to use
+    TooHardToUse,
+    /// The customer support was not satisfying
+    #[serde(rename = "UNSATIFIED_BY_CUSTOMER_SUPPORT")]
+    UnsatisfiedByCustomerSupport,
+    /// Another reason, which must be explained in the commentary
+    Other,
+}
+
+#[derive(Debug, Serialize)]
+struct OvhTerminationConfirm<'a> {
+    token: &'a str,
+    reason: OvhTerminationReason,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commentary: Option<&'a str>,
+}
+
+/// Termination requested with [`terminate`], waiting for the token sent by
+/// email to be confirmed.
+#[derive(Debug)]
+pub struct OvhPendingTermination {
+    service: String,
+    message: String,
+}
+
+impl OvhPendingTermination {
+    /// Resumes a termination requested earlier, for instance by another
+    /// process, for the service at the given path.
+    pub fn resume(service: &str) -> Self {
+        Self {
+            service: service.into(),
+            message: String::new(),
+        }
+    }
+
+    /// Path of the service being terminated.
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    /// Message returned by the API when the termination was requested,
+    /// empty for a resumed termination.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Confirms the termination with the token received by email.
+    ///
+    /// A commentary is mandatory with [`OvhTerminationReason::Other`]. The
+    /// service is terminated at its expiration date once confirmed.
+    pub async fn confirm_termination(
+        self,
+        c: &OvhClient,
+        token: &str,
+        reason: OvhTerminationReason,
+        commentary: Option<&str>,
+    ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        if token.trim().is_empty() {
+            return Err("missing termination token".into());
+        }
+        if reason == OvhTerminationReason::Other && commentary.is_none_or(|c| c.trim().is_empty()) {
+            return Err("a commentary is required to terminate for another reason".into());
+        }
+
+        let data = OvhTerminationConfirm {
+            token,
+            reason,
+            commentary,
+        };
+        let res = c
+            .post_json(&format!("{}/confirmTermination", self.service), &data)
+            .await?;
+        Ok(res)
+    }
+}
+
+/// Requests the termination of a service.
+///
+/// Nothing is terminated yet: OVH sends a token by email to the
+/// administrative contact of the service, which must be given to
+/// [`OvhPendingTermination::confirm_termination`] along with the reason of
+/// the termination.
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::service::{terminate, OvhTerminationReason};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let pending = terminate(&c, "/dedicated/server/ns1234.ip-1-2-3.eu")
+///         .await
+///         .unwrap();
+///     println!("{}", pending.message());
+///
+///     // Later, with the token received by email:
+///     pending
+///         .confirm_termination(&c, "token", OvhTerminationReason::NotNeededAnymore, None)
+///         .await
+///         .unwrap();
+/// }
+/// ```
+pub async fn terminate(
+    c: &OvhClient,
+    service: &str,
+) -> Result<OvhPendingTermination, Box<dyn std::error::Error + Send + Sync>> {
+    let message = c
+        .post_json(&format!("{}/terminate", service), &serde_json::json!({}))
+        .await?;
+    Ok(OvhPendingTermination {
+        service: service.into(),
+        message,
+    })
+}
@@ -0,0 +1,186 @@
+//! High-level access to the SMS API.
+
+use std::time::{Duration, Instant};
+
+use crate::client::OvhClient;
+
+use serde::{Deserialize, Serialize};
+
+/// Delay between two delivery status checks in
+/// [`OvhSms::send_and_wait`].
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Structure representing a message to send.
+#[derive(Debug, Serialize)]
+pub struct OvhSmsMessage {
+    /// Content of the message
+    pub message: String,
+    /// Phone numbers to send the message to, in international format
+    pub receivers: Vec<String>,
+    /// Sender of the message, or the default sender of the service if unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<String>,
+    /// Do not append the STOP clause to non-commercial messages
+    #[serde(rename(serialize = "noStopClause"))]
+    pub no_stop_clause: bool,
+}
+
+impl OvhSmsMessage {
+    /// Creates a message for the given receivers.
+    pub fn new(message: &str, receivers: &[&str]) -> Self {
+        Self {
+            message: message.into(),
+            receivers: receivers.iter().map(|&r| r.into()).collect(),
+            sender: None,
+            no_stop_clause: false,
+        }
+    }
+}
+
+/// Structure representing a submitted sending job.
+#[derive(Debug, Deserialize)]
+pub struct OvhSmsJob {
+    /// Identifiers of the created messages, one per valid receiver
+    pub ids: Vec<u64>,
+    /// Receivers the message will be sent to
+    #[serde(rename(deserialize = "validReceivers"))]
+    pub valid_receivers: Vec<String>,
+    /// Receivers that were rejected
+    #[serde(rename(deserialize = "invalidReceivers"))]
+    pub invalid_receivers: Vec<String>,
+    /// Credits consumed by the job
+    #[serde(rename(deserialize = "totalCreditsRemoved"))]
+    pub total_credits_removed: f64,
+}
+
+/// Structure representing a message that left the sending queue.
+#[derive(Debug, Deserialize)]
+pub struct OvhSmsOutgoing {
+    /// Unique identifier of the message
+    pub id: u64,
+    /// Phone number the message was sent to
+    pub receiver: String,
+    /// Raw delivery receipt code
+    #[serde(rename(deserialize = "deliveryReceipt"))]
+    pub delivery_receipt: u64,
+}
+
+impl OvhSmsOutgoing {
+    /// Interprets the delivery receipt code of the message.
+    pub fn status(&self) -> OvhSmsStatus {
+        match self.delivery_receipt {
+            1 => OvhSmsStatus::Delivered,
+            2 | 16 => OvhSmsStatus::Failed,
+            _ => OvhSmsStatus::Pending,
+        }
+    }
+}
+
+/// Delivery status of a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OvhSmsStatus {
+    /// The message has not reached its final state yet
+    Pending,
+    /// The message was delivered to the handset
+    Delivered,
+    /// The message could not be delivered
+    Failed,
+}
+
+/// Namespace for the SMS operations.
+pub struct OvhSms;
+
+impl OvhSms {
+    /// Submits a message for sending.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::sms::{OvhSms, OvhSmsMessage};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let msg = OvhSmsMessage::new("Hello!", &["+33600000000"]);
+    ///     let job = OvhSms::send(&c, "sms-ab12345-1", &msg).await.unwrap();
+    ///     println!("{:?}", job.ids);
+    /// }
+    /// ```
+    pub async fn send(
+        c: &OvhClient,
+        service: &str,
+        message: &OvhSmsMessage,
+    ) -> Result<OvhSmsJob, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .post_json(&format!("/sms/{}/jobs", service), message)
+            .await?;
+        Ok(res)
+    }
+
+    /// Retrieves a message that left the sending queue.
+    ///
+    /// Returns `None` while the message is still queued.
+    pub async fn outgoing(
+        c: &OvhClient,
+        service: &str,
+        id: u64,
+    ) -> Result<Option<OvhSmsOutgoing>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c
+            .get_opt(&format!("/sms/{}/outgoing/{}", service, id))
+            .await?;
+        Ok(res)
+    }
+
+    /// Submits a message and waits until every receiver either got it or
+    /// is known to have failed.
+    ///
+    /// Returns an error if the messages did not reach a final state before
+    /// `timeout` elapsed.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use ovh::client::OvhClient;
+    /// use ovh::sms::{OvhSms, OvhSmsMessage, OvhSmsStatus};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let msg = OvhSmsMessage::new("Disk full on db1", &["+33600000000"]);
+    ///     let sent = OvhSms::send_and_wait(&c, "sms-ab12345-1", &msg, Duration::from_secs(120))
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     for s in sent {
+    ///         assert_eq!(s.status(), OvhSmsStatus::Delivered);
+    ///     }
+    /// }
+    /// ```
+    pub async fn send_and_wait(
+        c: &OvhClient,
+        service: &str,
+        message: &OvhSmsMessage,
+        timeout: Duration,
+    ) -> Result<Vec<OvhSmsOutgoing>, Box<dyn std::error::Error + Send + Sync>> {
+        let deadline = Instant::now() + timeout;
+        let job = Self::send(c, service, message).await?;
+
+        loop {
+            let res =
+                futures::future::join_all(job.ids.iter().map(|&id| Self::outgoing(c, service, id)))
+                    .await;
+            let res = res.into_iter().collect::<Result<Vec<_>, _>>()?;
+
+            let done = res
+                .iter()
+                .all(|o| matches!(o, Some(o) if o.status() != OvhSmsStatus::Pending));
+            if done {
+                return Ok(res.into_iter().flatten().collect());
+            }
+
+            if Instant::now() + POLL_INTERVAL > deadline {
+                return Err("timed out waiting for SMS delivery".into());
+            }
+            c.sleep(POLL_INTERVAL).await;
+        }
+    }
+}
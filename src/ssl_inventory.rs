@@ -0,0 +1,208 @@
+//! Inventory of the TLS certificates served by the products of the account.
+//!
+//! [`CertificateInventory::collect`] gathers the certificates of the web
+//! hostings, IP Load Balancers and SSL Gateways of the account, so that
+//! renewal automation can find the ones expiring soon in a single place.
+
+use crate::client::OvhClient;
+use crate::date::{parse_day, today};
+use crate::fetch::fetch_all;
+use crate::hosting_web::OvhHostingWebSsl;
+use crate::ip_loadbalancing::OvhIpLoadbalancingSsl;
+
+use serde::Deserialize;
+
+/// Product serving a certificate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CertificateSource {
+    /// Web hosting
+    HostingWeb,
+    /// IP Load Balancer
+    IpLoadbalancing,
+    /// SSL Gateway
+    SslGateway,
+}
+
+/// Certificate found by a [`CertificateInventory`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateEntry {
+    /// Product serving the certificate
+    pub source: CertificateSource,
+    /// Name of the service serving the certificate
+    pub service: String,
+    /// Subject of the certificate, or the domain it is served for
+    pub name: String,
+    /// Expiration date of the certificate, as reported by the API, or
+    /// `None` if unknown, as for the certificates renewed automatically by
+    /// SSL Gateways
+    pub expiration: Option<String>,
+}
+
+impl CertificateEntry {
+    /// Returns the number of days until the certificate expires, negative
+    /// if it already has, or `None` if unknown.
+    pub fn days_left(&self) -> Option<i64> {
+        let day = parse_day(self.expiration.as_deref()?)?;
+        Some(day - today())
+    }
+}
+
+/// Domain served by an SSL Gateway.
+#[derive(Debug, Deserialize)]
+struct OvhSslGatewayDomain {
+    domain: String,
+}
+
+/// Certificates of every web hosting, IP Load Balancer and SSL Gateway of
+/// the account.
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::ssl_inventory::CertificateInventory;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let inventory = CertificateInventory::collect(&c).await.unwrap();
+///     for cert in inventory.expiring_within(30) {
+///         println!(
+///             "{:?} {} {}: expires on {}",
+///             cert.source,
+///             cert.service,
+///             cert.name,
+///             cert.expiration.as_deref().unwrap_or_default()
+///         );
+///     }
+///     for (service, e) in &inventory.failed {
+///         eprintln!("{}: {}", service, e);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct CertificateInventory {
+    /// Certificates found
+    pub certificates: Vec<CertificateEntry>,
+    /// Services whose certificates could not be retrieved, with the error
+    pub failed: Vec<(String, Box<dyn std::error::Error + Send + Sync>)>,
+}
+
+impl CertificateInventory {
+    /// Collects the certificates of every product of the account.
+    ///
+    /// Listing the services of a product failing makes the whole
+    /// collection fail, while a single service failing is reported in
+    /// [`failed`](Self::failed).
+    pub async fn collect(c: &OvhClient) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut inventory = Self::default();
+
+        let hostings: Vec<String> = c.get_json("/hosting/web").await?;
+        let res = fetch_all(&hostings, c.fetch_concurrency(), |service| async move {
+            let ssl = OvhHostingWebSsl::get(c, service).await?;
+            let entries = ssl.into_iter().map(|ssl| CertificateEntry {
+                source: CertificateSource::HostingWeb,
+                service: service.clone(),
+                name: service.clone(),
+                expiration: ssl.expiration_date,
+            });
+            Ok(entries.collect())
+        })
+        .await;
+        inventory.add(&hostings, res);
+
+        let lbs: Vec<String> = c.get_json("/ipLoadbalancing").await?;
+        let res = fetch_all(&lbs, c.fetch_concurrency(), |service| async move {
+            let certs = OvhIpLoadbalancingSsl::list(c, service).await?;
+            let entries = certs.into_iter().map(|ssl| CertificateEntry {
+                source: CertificateSource::IpLoadbalancing,
+                service: service.clone(),
+                name: ssl.subject,
+                expiration: Some(ssl.expire_date),
+            });
+            Ok(entries.collect())
+        })
+        .await;
+        inventory.add(&lbs, res);
+
+        let gateways: Vec<String> = c.get_json("/sslGateway").await?;
+        let res = fetch_all(&gateways, c.fetch_concurrency(), |service| async move {
+            let path = format!("/sslGateway/{}/domain", service);
+            let ids: Vec<u64> = c.get_json(&path).await?;
+            let mut entries = Vec::new();
+            for id in ids {
+                let d: OvhSslGatewayDomain = c.get_json(&format!("{}/{}", path, id)).await?;
+                entries.push(CertificateEntry {
+                    source: CertificateSource::SslGateway,
+                    service: service.clone(),
+                    name: d.domain,
+                    expiration: None,
+                });
+            }
+            Ok(entries)
+        })
+        .await;
+        inventory.add(&gateways, res);
+
+        Ok(inventory)
+    }
+
+    fn add(
+        &mut self,
+        services: &[String],
+        res: Vec<Result<Vec<CertificateEntry>, Box<dyn std::error::Error + Send + Sync>>>,
+    ) {
+        for (service, r) in services.iter().zip(res) {
+            match r {
+                Ok(entries) => self.certificates.extend(entries),
+                Err(e) => self.failed.push((service.clone(), e)),
+            }
+        }
+    }
+
+    /// Returns the certificates expiring within `days` days, expired ones
+    /// included, the soonest first.
+    ///
+    /// ```
+    /// use ovh::ssl_inventory::{CertificateEntry, CertificateInventory, CertificateSource};
+    ///
+    /// let entry = |name: &str, expiration: Option<&str>| CertificateEntry {
+    ///     source: CertificateSource::IpLoadbalancing,
+    ///     service: "loadbalancer-xxx".into(),
+    ///     name: name.into(),
+    ///     expiration: expiration.map(String::from),
+    /// };
+    /// let inventory = CertificateInventory {
+    ///     certificates: vec![
+    ///         entry("www.example.com", Some("2001-01-01T00:00:00+01:00")),
+    ///         entry("api.example.com", Some("9999-12-31T00:00:00+01:00")),
+    ///         entry("old.example.com", Some("2000-06-01")),
+    ///         entry("shop.example.com", None),
+    ///     ],
+    ///     failed: Vec::new(),
+    /// };
+    ///
+    /// let names: Vec<_> = inventory.expiring_within(30).iter().map(|c| &c.name).collect();
+    /// assert_eq!(names, ["old.example.com", "www.example.com"]);
+    /// assert_eq!(inventory.unknown_expiration()[0].name, "shop.example.com");
+    /// ```
+    pub fn expiring_within(&self, days: i64) -> Vec<&CertificateEntry> {
+        let mut res: Vec<_> = self
+            .certificates
+            .iter()
+            .filter(|c| c.days_left().is_some_and(|d| d <= days))
+            .collect();
+        res.sort_by(|a, b| {
+            a.days_left()
+                .cmp(&b.days_left())
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        res
+    }
+
+    /// Returns the certificates whose expiration date is unknown.
+    pub fn unknown_expiration(&self) -> Vec<&CertificateEntry> {
+        self.certificates
+            .iter()
+            .filter(|c| c.days_left().is_none())
+            .collect()
+    }
+}
@@ -0,0 +1,223 @@
+//! Status of the OVH products: ongoing incidents and maintenances.
+//!
+//! When the API fails with server errors, the cause is often an incident
+//! already known to OVH. [`annotate_error`] looks for such incidents on a
+//! product and attaches them to the error, so that operators see right
+//! away that the failure is probably not on their side.
+
+use crate::client::OvhClient;
+use crate::error::{OvhAttemptOutcome, OvhError};
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+/// Structure representing an incident or a maintenance on OVH products.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OvhStatusTask {
+    /// Unique identifier of the task
+    pub id: u64,
+    /// Short description of the task
+    pub title: String,
+    /// Kind of task (`incident`, `upgrade`, `maintenance`...)
+    #[serde(rename(deserialize = "type"))]
+    pub task_type: String,
+    /// Current status of the task (`planned`, `inProgress`, `finished`)
+    pub status: String,
+    /// Product family affected, such as `cloud` or `domain`
+    pub category: Option<String>,
+    /// Product affected, such as `Public Cloud` or `DNS`
+    pub project: Option<String>,
+    /// Services affected
+    #[serde(rename(deserialize = "impactedService"))]
+    pub impacted_service: Option<String>,
+    /// Level of impact (`none`, `partial`, `total`...)
+    pub impact: Option<String>,
+    /// Start date of the task
+    #[serde(rename(deserialize = "startDate"))]
+    pub start_date: Option<String>,
+    /// End date of the task, once finished
+    #[serde(rename(deserialize = "endDate"))]
+    pub end_date: Option<String>,
+}
+
+impl OvhStatusTask {
+    /// Lists the incidents and maintenances on the products of the region
+    /// of the endpoint.
+    pub async fn list(
+        c: &OvhClient,
+    ) -> Result<Vec<OvhStatusTask>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json("/status/task").await?;
+        Ok(res)
+    }
+
+    /// Whether the task is an incident not yet resolved.
+    pub fn is_ongoing_incident(&self) -> bool {
+        self.task_type == "incident" && self.status != "finished"
+    }
+
+    /// Whether the task affects a product, matched without regard to case
+    /// against its category, project and impacted services.
+    ///
+    /// ```
+    /// use ovh::status::OvhStatusTask;
+    ///
+    /// let task: OvhStatusTask = serde_json::from_str(
+    ///     r#"{
+    ///         "id": 1,
+    ///         "title": "DNS resolution delays",
+    ///         "type": "incident",
+    ///         "status": "inProgress",
+    ///         "category": "domain",
+    ///         "project": "DNS"
+    ///     }"#,
+    /// )
+    /// .unwrap();
+    /// assert!(task.is_ongoing_incident());
+    /// assert!(task.affects("dns"));
+    /// assert!(!task.affects("cloud"));
+    /// ```
+    pub fn affects(&self, product: &str) -> bool {
+        let product = product.to_lowercase();
+        [&self.category, &self.project, &self.impacted_service]
+            .iter()
+            .filter_map(|f| f.as_deref())
+            .any(|f| f.to_lowercase().contains(&product))
+    }
+
+    /// Whether the task affects a region or datacenter, such as `GRA` or
+    /// `BHS`, matched without regard to case against its title and
+    /// impacted services.
+    ///
+    /// ```
+    /// use ovh::status::OvhStatusTask;
+    ///
+    /// let task: OvhStatusTask = serde_json::from_str(
+    ///     r#"{
+    ///         "id": 1,
+    ///         "title": "Instances unreachable",
+    ///         "type": "incident",
+    ///         "status": "inProgress",
+    ///         "impactedService": "Public Cloud GRA11"
+    ///     }"#,
+    /// )
+    /// .unwrap();
+    /// assert!(task.affects_region("gra"));
+    /// assert!(!task.affects_region("BHS"));
+    /// ```
+    pub fn affects_region(&self, region: &str) -> bool {
+        let region = region.to_lowercase();
+        [Some(&self.title), self.impacted_service.as_ref()]
+            .iter()
+            .flatten()
+            .any(|f| f.to_lowercase().contains(&region))
+    }
+
+    /// Lists the incidents not yet resolved affecting a product, and a
+    /// region if one is given.
+    pub async fn ongoing_incidents(
+        c: &OvhClient,
+        product: &str,
+        region: Option<&str>,
+    ) -> Result<Vec<OvhStatusTask>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = Self::list(c)
+            .await?
+            .into_iter()
+            .filter(|t| {
+                t.is_ongoing_incident()
+                    && t.affects(product)
+                    && region.is_none_or(|r| t.affects_region(r))
+            })
+            .collect();
+        Ok(res)
+    }
+}
+
+/// Attaches the ongoing incidents on a product to a server error.
+///
+/// If `err` is a server error and incidents affecting `product`, and
+/// `region` if one is given, are ongoing, it is wrapped in an
+/// [`OvhError::PossibleIncident`] naming them. Server errors are
+/// [`OvhError::Api`] errors with a 5xx status, as returned by
+/// [`OvhClient::ensure_success`], [`OvhError::RetriesExhausted`] errors
+/// with at least one attempt answered with a 5xx status, and `reqwest`
+/// errors with a 5xx status, as returned by `Response::error_for_status`.
+/// Any other error, or one for which the status could not be retrieved,
+/// is returned unchanged:
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use ovh::client::OvhClient;
+/// use ovh::error::{OvhAttempt, OvhAttemptOutcome, OvhError};
+/// use ovh::status::annotate_error;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key").unwrap();
+///     // Only rate limited: the status is not even looked up.
+///     let attempt = OvhAttempt {
+///         outcome: OvhAttemptOutcome::Status(reqwest::StatusCode::TOO_MANY_REQUESTS),
+///         elapsed: Duration::from_millis(120),
+///     };
+///     let err = OvhError::RetriesExhausted { attempts: vec![attempt.clone(), attempt] };
+///     let err = annotate_error(&c, "dns", None, err.into()).await;
+///     assert!(matches!(err.downcast_ref(), Some(OvhError::RetriesExhausted { .. })));
+/// }
+/// ```
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::dns_zone::OvhDnsZone;
+/// use ovh::status::annotate_error;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     if let Err(e) = OvhDnsZone::refresh(&c, "example.com").await {
+///         let e = annotate_error(&c, "dns", None, e).await;
+///         eprintln!("{}", e);
+///     }
+/// }
+/// ```
+pub async fn annotate_error(
+    c: &OvhClient,
+    product: &str,
+    region: Option<&str>,
+    err: Box<dyn std::error::Error + Send + Sync>,
+) -> Box<dyn std::error::Error + Send + Sync> {
+    let is_server_error = match err.downcast_ref::<OvhError>() {
+        Some(OvhError::Api { status, .. }) => status.is_server_error(),
+        Some(OvhError::RetriesExhausted { attempts }) => attempts
+            .iter()
+            .any(|a| matches!(a.outcome, OvhAttemptOutcome::Status(s) if s.is_server_error())),
+        Some(_) => false,
+        None => err
+            .downcast_ref::<reqwest::Error>()
+            .and_then(|e| e.status())
+            .is_some_and(|s| s.is_server_error()),
+    };
+    if !is_server_error {
+        return err;
+    }
+
+    let incidents = match OvhStatusTask::ongoing_incidents(c, product, region).await {
+        Ok(i) if !i.is_empty() => i,
+        _ => return err,
+    };
+    let error = match err.downcast::<OvhError>() {
+        Ok(e) => e,
+        Err(err) => match err.downcast::<reqwest::Error>() {
+            Ok(e) => Box::new(OvhError::Api {
+                status: e.status().unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                error_code: None,
+                message: e.to_string(),
+                query_id: None,
+            }),
+            Err(err) => return err,
+        },
+    };
+    Box::new(OvhError::PossibleIncident {
+        error,
+        incidents: incidents.into_iter().map(|i| i.title).collect(),
+    })
+}
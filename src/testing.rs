@@ -0,0 +1,228 @@
+//! Mock OVH API server for tests, shipped with the `test-util` feature.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use secrecy::SecretString;
+use wiremock::{
+    matchers::{method, path, query_param},
+    Match, Mock, MockServer, Request, Respond, ResponseTemplate,
+};
+
+use crate::client::OvhClient;
+use crate::middleware::{SignatureScheme, Signer};
+
+/// Maximum difference, in seconds, between the timestamp of a request and
+/// the time of the server.
+const TIMESTAMP_TOLERANCE: i64 = 180;
+
+/// Credentials used to check the requests received by a [`MockOvhServer`].
+#[derive(Debug, Clone)]
+struct Credentials {
+    application_key: String,
+    application_secret: String,
+    consumer_key: String,
+}
+
+/// Why a request is rejected by the server, if it is.
+#[derive(Debug, PartialEq, Eq)]
+enum Rejection {
+    InvalidKey,
+    InvalidSignature,
+    OutdatedRequest,
+}
+
+impl Credentials {
+    fn check(&self, req: &Request) -> Option<Rejection> {
+        let header = |name: &str| req.headers.get(name).and_then(|v| v.to_str().ok());
+
+        if header("X-Ovh-Application") != Some(&self.application_key)
+            || header("X-Ovh-Consumer") != Some(&self.consumer_key)
+        {
+            return Some(Rejection::InvalidKey);
+        }
+        let timestamp = match header("X-Ovh-Timestamp").map(str::parse::<i64>) {
+            Some(Ok(t)) => t,
+            _ => return Some(Rejection::InvalidSignature),
+        };
+        if (timestamp - now()).abs() > TIMESTAMP_TOLERANCE {
+            return Some(Rejection::OutdatedRequest);
+        }
+
+        let signature = header("X-Ovh-Signature").unwrap_or_default();
+        let mut signer = Signer::new(
+            SecretString::new(self.application_secret.clone()),
+            SecretString::new(self.consumer_key.clone()),
+        );
+        signer.scheme = match signature.get(..3) {
+            Some("$2$") => SignatureScheme::Sha256,
+            _ => SignatureScheme::Sha1,
+        };
+        // Wiremock rewrites the host of the URL, so the signed one is
+        // rebuilt from the `Host` header.
+        let url = format!(
+            "{}://{}{}{}",
+            req.url.scheme(),
+            header("Host").unwrap_or_default(),
+            req.url.path(),
+            req.url
+                .query()
+                .map(|q| format!("?{}", q))
+                .unwrap_or_default()
+        );
+        let expected = signer.signature(
+            &url,
+            &timestamp.to_string(),
+            req.method.as_str(),
+            &String::from_utf8_lossy(&req.body),
+        );
+        if signature != expected {
+            return Some(Rejection::InvalidSignature);
+        }
+        None
+    }
+}
+
+/// Matches the requests carrying valid authentication headers.
+struct Signed(Credentials);
+
+impl Match for Signed {
+    fn matches(&self, request: &Request) -> bool {
+        self.0.check(request).is_none()
+    }
+}
+
+/// Answers the requests not matching any route like the API would.
+struct Fallback(Credentials);
+
+impl Respond for Fallback {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let (status, code, message) = match self.0.check(request) {
+            Some(Rejection::InvalidKey) => (403, "INVALID_CREDENTIAL", "Invalid credential"),
+            Some(Rejection::InvalidSignature) => (400, "INVALID_SIGNATURE", "Invalid signature"),
+            Some(Rejection::OutdatedRequest) => (400, "OUTDATED_REQUEST", "Outdated request"),
+            None => (404, "NOT_FOUND", "No route mocked for this request"),
+        };
+        let body = serde_json::json!({ "errorCode": code, "message": message });
+        ResponseTemplate::new(status).set_body_json(body)
+    }
+}
+
+/// Local HTTP server mimicking the OVH API.
+///
+/// The server answers `/auth/time` with its local time and the mocked
+/// routes with their canned responses, but only to requests carrying the
+/// application key and consumer key it was started with, a recent
+/// timestamp, and a valid SHA-1 or SHA-256 signature. Other requests are
+/// rejected with the error codes of the API (`INVALID_SIGNATURE`,
+/// `OUTDATED_REQUEST`...), so tests check both the payloads sent and that
+/// they are correctly signed.
+///
+/// ```
+/// use ovh::testing::MockOvhServer;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let server = MockOvhServer::start("app_key", "app_secret", "consumer_key").await;
+///     server.on("GET", "/domain/zone", 200, r#"["example.com"]"#).await;
+///
+///     let c = server.client();
+///     let zones: Vec<String> = c.get_json("/domain/zone").await.unwrap();
+///     assert_eq!(zones, ["example.com"]);
+///
+///     // A client with another secret gets its requests rejected.
+///     let c = ovh::client::OvhClient::new(&server.uri(), "app_key", "wrong", "consumer_key")
+///         .unwrap();
+///     assert!(c.get_json::<Vec<String>>("/domain/zone").await.is_err());
+/// }
+/// ```
+pub struct MockOvhServer {
+    server: MockServer,
+    credentials: Credentials,
+}
+
+impl MockOvhServer {
+    /// Starts a server accepting the requests signed with the given
+    /// credentials.
+    pub async fn start(
+        application_key: &str,
+        application_secret: &str,
+        consumer_key: &str,
+    ) -> Self {
+        let server = MockServer::start().await;
+        let credentials = Credentials {
+            application_key: application_key.into(),
+            application_secret: application_secret.into(),
+            consumer_key: consumer_key.into(),
+        };
+
+        Mock::given(method("GET"))
+            .and(path("/1.0/auth/time"))
+            .respond_with(|_: &Request| {
+                ResponseTemplate::new(200).set_body_string(now().to_string())
+            })
+            .with_priority(1)
+            .mount(&server)
+            .await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(Fallback(credentials.clone()))
+            .with_priority(u8::MAX)
+            .mount(&server)
+            .await;
+
+        Self {
+            server,
+            credentials,
+        }
+    }
+
+    /// Returns the URL of the server, to be used as a custom endpoint.
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// Returns a client of the server using its credentials.
+    pub fn client(&self) -> OvhClient {
+        OvhClient::builder(
+            self.uri(),
+            &self.credentials.application_key,
+            &self.credentials.application_secret,
+            &self.credentials.consumer_key,
+        )
+        .build()
+        .expect("the credentials of the server are valid")
+    }
+
+    /// Answers the signed requests to a route with a status and a JSON
+    /// body.
+    ///
+    /// The path is relative to the API version and may hold a query
+    /// string, whose parameters must then all be present. When several
+    /// routes match a request, the first one added answers it.
+    pub async fn on(&self, http_method: &str, route: &str, status: u16, body: &str) {
+        let (route, query) = route.split_once('?').unwrap_or((route, ""));
+        let mut mock = Mock::given(method(http_method))
+            .and(path(format!("/1.0{}", route)))
+            .and(Signed(self.credentials.clone()));
+        for pair in query.split('&').filter(|p| !p.is_empty()) {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            mock = mock.and(query_param(k, v));
+        }
+        mock.respond_with(ResponseTemplate::new(status).set_body_raw(body, "application/json"))
+            .with_priority(2)
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Returns the requests received so far, oldest first, time
+    /// synchronization ones included.
+    pub async fn received_requests(&self) -> Vec<Request> {
+        self.server.received_requests().await.unwrap_or_default()
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,56 @@
+//! Timers used to wait between requests.
+//!
+//! Clients wait when retrying requests, respecting a rate limit or polling
+//! a long-running operation, such as an order delivery. They use Tokio
+//! timers by default; another [`Timer`] can be given to
+//! [`OvhClient::with_timer`](crate::client::OvhClient::with_timer) to run
+//! under another async runtime, such as `async-std` or `smol`. The default
+//! transport also needs a Tokio runtime, so such clients need another
+//! [`HttpTransport`](crate::transport::HttpTransport) as well.
+
+use std::{future::Future, pin::Pin, time::Duration};
+
+/// Future returned by [`Timer::sleep`].
+pub type SleepFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Waits for durations of time.
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use ovh::client::OvhClient;
+/// use ovh::timer::{SleepFuture, Timer};
+///
+/// struct ThreadTimer;
+///
+/// impl Timer for ThreadTimer {
+///     fn sleep(&self, duration: Duration) -> SleepFuture {
+///         let (tx, rx) = futures::channel::oneshot::channel();
+///         std::thread::spawn(move || {
+///             std::thread::sleep(duration);
+///             let _ = tx.send(());
+///         });
+///         Box::pin(async move {
+///             let _ = rx.await;
+///         })
+///     }
+/// }
+///
+/// let client = OvhClient::new("ovh-eu", "app_key", "app_secret", "consumer_key")
+///     .unwrap()
+///     .with_timer(ThreadTimer);
+/// ```
+pub trait Timer: Send + Sync {
+    /// Returns a future completing after `duration`.
+    fn sleep(&self, duration: Duration) -> SleepFuture;
+}
+
+/// Timer of the Tokio runtime, used by default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioTimer;
+
+impl Timer for TokioTimer {
+    fn sleep(&self, duration: Duration) -> SleepFuture {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
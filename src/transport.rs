@@ -0,0 +1,211 @@
+//! HTTP layer used by clients to send requests.
+//!
+//! Clients send requests through [`reqwest`] by default. Another
+//! [`HttpTransport`] can be given to
+//! [`OvhClientBuilder::transport`](crate::client::OvhClientBuilder::transport),
+//! such as the in-memory [`MockTransport`] shipped with the `test-util`
+//! feature to test code using the high-level modules without reaching the
+//! API. The same feature ships a [`RecordingTransport`] saving real
+//! interactions to a [`Cassette`], replayed offline by a
+//! [`ReplayTransport`].
+
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use reqwest::Response;
+
+use crate::middleware::OvhRequest;
+
+/// Future returned by [`HttpTransport::send`].
+pub type TransportFuture<'a> = Pin<
+    Box<
+        dyn Future<Output = Result<Response, Box<dyn std::error::Error + Send + Sync>>> + Send + 'a,
+    >,
+>;
+
+/// Sends signed requests and returns their responses.
+///
+/// Network errors of the default transport that are worth retrying
+/// (connection failures and timeouts) are retried by idempotent requests.
+/// Errors of other transports are returned as is.
+pub trait HttpTransport: Send + Sync {
+    /// Sends a request.
+    fn send<'a>(&'a self, req: &'a OvhRequest) -> TransportFuture<'a>;
+}
+
+impl<T: HttpTransport + ?Sized> HttpTransport for Arc<T> {
+    fn send<'a>(&'a self, req: &'a OvhRequest) -> TransportFuture<'a> {
+        (**self).send(req)
+    }
+}
+
+/// Transport sending requests with a [`reqwest::Client`].
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport from an HTTP client.
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send<'a>(&'a self, req: &'a OvhRequest) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let mut builder = self
+                .client
+                .request(req.method.clone(), &req.url)
+                .headers(req.headers.clone());
+            if !req.body.is_empty() {
+                builder = builder
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(req.body.clone());
+            }
+            Ok(builder.send().await?)
+        })
+    }
+}
+
+#[cfg(feature = "test-util")]
+pub use mock::MockTransport;
+#[cfg(feature = "test-util")]
+pub use vcr::{
+    Cassette, CassetteInteraction, RecordingTransport, ReplayTransport, DEFAULT_REDACTED_KEYS,
+};
+
+#[cfg(feature = "test-util")]
+mod vcr;
+
+/// Strips the scheme, host and API version from a URL.
+#[cfg(feature = "test-util")]
+fn relative_path(url: &str) -> &str {
+    let path = url
+        .find("://")
+        .and_then(|i| url[i + 3..].find('/').map(|j| &url[i + 3 + j..]))
+        .unwrap_or(url);
+    ["/1.0", "/v1", "/v2"]
+        .iter()
+        .find_map(|p| path.strip_prefix(p).filter(|r| r.starts_with('/')))
+        .unwrap_or(path)
+}
+
+#[cfg(feature = "test-util")]
+mod mock {
+    use std::{
+        sync::Mutex,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use reqwest::{Method, Response};
+
+    use super::{relative_path, HttpTransport, TransportFuture};
+    use crate::middleware::OvhRequest;
+
+    struct MockRoute {
+        method: Method,
+        path: String,
+        status: u16,
+        body: String,
+    }
+
+    /// In-memory transport answering requests with canned responses.
+    ///
+    /// Routes are matched on their method and path, relative to the API
+    /// version and including the query string. Requests to unknown routes
+    /// get a 404 error, except `/auth/time` which answers with the local
+    /// time. Every request received is recorded.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use ovh::client::OvhClient;
+    /// use ovh::transport::MockTransport;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mock = Arc::new(MockTransport::new());
+    ///     mock.on("GET", "/domain/zone", 200, r#"["example.com"]"#);
+    ///
+    ///     let c = OvhClient::builder("ovh-eu", "app_key", "app_secret", "consumer_key")
+    ///         .transport(mock.clone())
+    ///         .build()
+    ///         .unwrap();
+    ///
+    ///     let zones: Vec<String> = c.get_json("/domain/zone").await.unwrap();
+    ///     assert_eq!(zones, ["example.com"]);
+    ///     assert_eq!(mock.requests().len(), 1);
+    /// }
+    /// ```
+    #[derive(Default)]
+    pub struct MockTransport {
+        routes: Mutex<Vec<MockRoute>>,
+        requests: Mutex<Vec<OvhRequest>>,
+    }
+
+    impl MockTransport {
+        /// Creates a transport without any route.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Answers the requests to a route with a status and a JSON body.
+        ///
+        /// Routes added later take precedence over earlier ones.
+        pub fn on(&self, method: &str, path: &str, status: u16, body: &str) -> &Self {
+            let method = Method::from_bytes(method.as_bytes()).unwrap_or(Method::GET);
+            self.routes.lock().unwrap().push(MockRoute {
+                method,
+                path: path.into(),
+                status,
+                body: body.into(),
+            });
+            self
+        }
+
+        /// Returns the requests received so far, oldest first, excluding
+        /// the time synchronization ones.
+        pub fn requests(&self) -> Vec<OvhRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        fn respond(&self, req: &OvhRequest) -> (u16, String) {
+            let path = relative_path(&req.url);
+
+            let routes = self.routes.lock().unwrap();
+            if let Some(r) = routes
+                .iter()
+                .rev()
+                .find(|r| r.method == req.method && r.path == path)
+            {
+                return (r.status, r.body.clone());
+            }
+            drop(routes);
+
+            if req.method == Method::GET && path == "/auth/time" {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or_default();
+                return (200, now.to_string());
+            }
+
+            let message = format!("no mock for {} {}", req.method, path);
+            (404, serde_json::json!({ "message": message }).to_string())
+        }
+    }
+
+    impl HttpTransport for MockTransport {
+        fn send<'a>(&'a self, req: &'a OvhRequest) -> TransportFuture<'a> {
+            Box::pin(async move {
+                let (status, body) = self.respond(req);
+                if relative_path(&req.url) != "/auth/time" {
+                    self.requests.lock().unwrap().push(req.clone());
+                }
+
+                let resp = http::Response::builder().status(status).body(body)?;
+                Ok(Response::from(resp))
+            })
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::Path,
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+
+use super::{relative_path, HttpTransport, TransportFuture};
+use crate::middleware::OvhRequest;
+
+/// Keys of JSON bodies and query strings redacted by default when
+/// recording, see [`RecordingTransport::redacted_keys`].
+pub const DEFAULT_REDACTED_KEYS: [&str; 6] = [
+    "consumerKey",
+    "applicationSecret",
+    "password",
+    "secret",
+    "key",
+    "token",
+];
+
+/// Value replacing the redacted secrets.
+const REDACTED: &str = "[REDACTED]";
+
+/// Interaction with the API recorded in a [`Cassette`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CassetteInteraction {
+    /// HTTP method of the request
+    pub method: String,
+    /// Path of the request, relative to the API version and including the
+    /// query string
+    pub path: String,
+    /// Body of the request, empty if none
+    #[serde(default)]
+    pub request_body: String,
+    /// Status of the response
+    pub status: u16,
+    /// Headers of the response
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// Body of the response
+    pub body: String,
+}
+
+/// Interactions with the API, saved as JSON to be replayed by a
+/// [`ReplayTransport`].
+///
+/// Cassettes never hold the authentication headers of the requests, and
+/// the values of the keys of JSON bodies and query strings listed in
+/// [`DEFAULT_REDACTED_KEYS`], or given to
+/// [`RecordingTransport::redacted_keys`], are redacted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cassette {
+    /// Interactions, in the order they happened
+    pub interactions: Vec<CassetteInteraction>,
+}
+
+impl Cassette {
+    /// Reads a cassette from a JSON file.
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the cassette to a JSON file.
+    pub fn save<T: AsRef<Path>>(
+        &self,
+        path: T,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Transport recording the interactions of another transport, usually the
+/// default one talking to the real API, into a [`Cassette`].
+///
+/// Time synchronization requests are not recorded. Secrets are redacted
+/// from the JSON bodies and query strings, the keys holding them being
+/// matched without regard to case:
+///
+/// ```
+/// use ovh::client::OvhClient;
+/// use ovh::transport::{Cassette, RecordingTransport, ReplayTransport};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Stands for the real API.
+///     let api = ReplayTransport::new(serde_json::from_str::<Cassette>(
+///         r#"{"interactions": [{
+///             "method": "POST",
+///             "path": "/me/sshKey?token=[REDACTED]",
+///             "status": 200,
+///             "body": "null"
+///         }]}"#,
+///     ).unwrap());
+///     let recorder = Arc::new(RecordingTransport::new(api));
+///     let c = OvhClient::builder("ovh-eu", "app_key", "app_secret", "consumer_key")
+///         .transport(recorder.clone())
+///         .build()
+///         .unwrap();
+///
+///     let body = serde_json::json!({ "keyName": "laptop", "key": "ssh-ed25519 AAAA..." });
+///     c.post("/me/sshKey?token=abc", &body).await.unwrap();
+///
+///     let interaction = &recorder.cassette().interactions[0];
+///     assert_eq!(interaction.path, "/me/sshKey?token=[REDACTED]");
+///     assert_eq!(
+///         interaction.request_body,
+///         r#"{"key":"[REDACTED]","keyName":"laptop"}"#
+///     );
+/// }
+/// ```
+///
+/// Recording against the real API:
+///
+/// ```no_run
+/// use std::sync::Arc;
+///
+/// use ovh::client::OvhClient;
+/// use ovh::dns_zone::OvhDnsZone;
+/// use ovh::transport::{RecordingTransport, ReqwestTransport};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let recorder = Arc::new(RecordingTransport::new(ReqwestTransport::default()));
+///     let c = OvhClient::builder("ovh-eu", "app_key", "app_secret", "consumer_key")
+///         .transport(recorder.clone())
+///         .build()
+///         .unwrap();
+///
+///     OvhDnsZone::list_names(&c).await.unwrap();
+///     recorder.cassette().save("tests/cassettes/zones.json").unwrap();
+/// }
+/// ```
+pub struct RecordingTransport<T> {
+    inner: T,
+    cassette: Mutex<Cassette>,
+    redacted_keys: Vec<String>,
+}
+
+impl<T: HttpTransport> RecordingTransport<T> {
+    /// Creates a transport recording the interactions of `inner`, with the
+    /// [`DEFAULT_REDACTED_KEYS`] redacted.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            cassette: Mutex::default(),
+            redacted_keys: default_redacted_keys(),
+        }
+    }
+
+    /// Sets the keys of JSON bodies and query strings whose values are
+    /// redacted, replacing the [`DEFAULT_REDACTED_KEYS`].
+    ///
+    /// The cassette must be replayed by a [`ReplayTransport`] redacting the
+    /// same keys, for the query strings to match.
+    pub fn redacted_keys(mut self, keys: &[&str]) -> Self {
+        self.redacted_keys = keys.iter().map(|&k| k.into()).collect();
+        self
+    }
+
+    /// Returns the interactions recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+}
+
+impl<T: HttpTransport> HttpTransport for RecordingTransport<T> {
+    fn send<'a>(&'a self, req: &'a OvhRequest) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let resp = self.inner.send(req).await?;
+            let path = relative_path(&req.url);
+            if path == "/auth/time" {
+                return Ok(resp);
+            }
+
+            let status = resp.status();
+            let headers = resp.headers().clone();
+            let body = resp.bytes().await?;
+            let text = String::from_utf8_lossy(&body);
+            self.cassette
+                .lock()
+                .unwrap()
+                .interactions
+                .push(CassetteInteraction {
+                    method: req.method.to_string(),
+                    path: redact_query(path, &self.redacted_keys),
+                    request_body: redact(&req.body, &self.redacted_keys),
+                    status: status.as_u16(),
+                    headers: headers
+                        .iter()
+                        .filter(|(_, v)| !v.is_sensitive())
+                        .filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_string())))
+                        .collect(),
+                    body: redact(&text, &self.redacted_keys),
+                });
+
+            let mut builder = http::Response::builder().status(status);
+            if let Some(h) = builder.headers_mut() {
+                *h = headers;
+            }
+            Ok(Response::from(builder.body(body)?))
+        })
+    }
+}
+
+/// Transport answering requests with the interactions of a [`Cassette`],
+/// without reaching the API.
+///
+/// A request is answered with the first interaction not replayed yet
+/// having its method and path, or with the last one if they were all
+/// replayed, so that polling loops terminate. Requests without matching
+/// interaction get a 404 error, except `/auth/time` which answers with the
+/// local time. Query strings are redacted like when recording before
+/// being compared.
+///
+/// ```
+/// use ovh::client::OvhClient;
+/// use ovh::dns_zone::OvhDnsZone;
+/// use ovh::transport::{Cassette, ReplayTransport};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     // Usually read with `Cassette::load` from a file saved while
+///     // recording.
+///     let cassette: Cassette = serde_json::from_str(
+///         r#"{"interactions": [{
+///             "method": "GET",
+///             "path": "/domain/zone",
+///             "status": 200,
+///             "body": "[\"example.com\"]"
+///         }]}"#,
+///     )
+///     .unwrap();
+///     let c = OvhClient::builder("ovh-eu", "app_key", "app_secret", "consumer_key")
+///         .transport(ReplayTransport::new(cassette))
+///         .build()
+///         .unwrap();
+///
+///     assert_eq!(OvhDnsZone::list_names(&c).await.unwrap(), ["example.com"]);
+/// }
+/// ```
+pub struct ReplayTransport {
+    cassette: Cassette,
+    replayed: Mutex<HashSet<usize>>,
+    redacted_keys: Vec<String>,
+}
+
+impl ReplayTransport {
+    /// Creates a transport replaying a cassette recorded with the
+    /// [`DEFAULT_REDACTED_KEYS`] redacted.
+    pub fn new(cassette: Cassette) -> Self {
+        Self {
+            cassette,
+            replayed: Mutex::default(),
+            redacted_keys: default_redacted_keys(),
+        }
+    }
+
+    /// Sets the keys of query strings redacted when the cassette was
+    /// recorded, see [`RecordingTransport::redacted_keys`].
+    pub fn redacted_keys(mut self, keys: &[&str]) -> Self {
+        self.redacted_keys = keys.iter().map(|&k| k.into()).collect();
+        self
+    }
+
+    /// Returns the interactions of the cassette not replayed yet.
+    pub fn remaining(&self) -> Vec<&CassetteInteraction> {
+        let replayed = self.replayed.lock().unwrap();
+        self.cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !replayed.contains(i))
+            .map(|(_, r)| r)
+            .collect()
+    }
+
+    fn respond(&self, req: &OvhRequest) -> (u16, Vec<(String, String)>, String) {
+        let path = redact_query(relative_path(&req.url), &self.redacted_keys);
+        let path = path.as_str();
+        let method = req.method.as_str();
+
+        let mut replayed = self.replayed.lock().unwrap();
+        let mut matching = self
+            .cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.method == method && r.path == path);
+        let found = matching
+            .clone()
+            .find(|(i, _)| !replayed.contains(i))
+            .or_else(|| matching.next_back());
+        if let Some((i, r)) = found {
+            replayed.insert(i);
+            return (r.status, r.headers.clone(), r.body.clone());
+        }
+
+        if path == "/auth/time" {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default();
+            return (200, Vec::new(), now.to_string());
+        }
+
+        let message = format!("no interaction for {} {}", method, path);
+        let body = serde_json::json!({ "message": message }).to_string();
+        (404, Vec::new(), body)
+    }
+}
+
+impl HttpTransport for ReplayTransport {
+    fn send<'a>(&'a self, req: &'a OvhRequest) -> TransportFuture<'a> {
+        Box::pin(async move {
+            let (status, headers, body) = self.respond(req);
+            let mut builder = http::Response::builder().status(status);
+            for (k, v) in &headers {
+                builder = builder.header(k.as_str(), v.as_str());
+            }
+            Ok(Response::from(builder.body(body)?))
+        })
+    }
+}
+
+fn default_redacted_keys() -> Vec<String> {
+    DEFAULT_REDACTED_KEYS.iter().map(|&k| k.into()).collect()
+}
+
+fn is_redacted(key: &str, keys: &[String]) -> bool {
+    keys.iter().any(|k| k.eq_ignore_ascii_case(key))
+}
+
+/// Redacts the secrets of a JSON body, returning other bodies untouched.
+fn redact(body: &str, keys: &[String]) -> String {
+    fn walk(v: &mut serde_json::Value, keys: &[String]) {
+        match v {
+            serde_json::Value::Object(map) => {
+                for (k, v) in map.iter_mut() {
+                    if is_redacted(k, keys) {
+                        *v = REDACTED.into();
+                    } else {
+                        walk(v, keys);
+                    }
+                }
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(|v| walk(v, keys)),
+            _ => {}
+        }
+    }
+
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(mut v) => {
+            walk(&mut v, keys);
+            v.to_string()
+        }
+        Err(_) => body.to_string(),
+    }
+}
+
+/// Redacts the secrets of the query string of a path.
+fn redact_query(path: &str, keys: &[String]) -> String {
+    let (route, query) = match path.split_once('?') {
+        Some(split) => split,
+        None => return path.into(),
+    };
+    let params: Vec<String> = query
+        .split('&')
+        .map(|param| match param.split_once('=') {
+            Some((k, _)) if is_redacted(k, keys) => format!("{}={}", k, REDACTED),
+            _ => param.into(),
+        })
+        .collect();
+    format!("{}?{}", route, params.join("&"))
+}
@@ -0,0 +1,47 @@
+//! High-level access to the VPS API.
+
+use crate::client::OvhClient;
+
+use serde::Deserialize;
+
+/// Structure representing a VPS.
+#[derive(Debug, Deserialize)]
+pub struct OvhVps {
+    /// Name of the VPS
+    pub name: String,
+    /// Name given to the VPS by its owner
+    #[serde(rename(deserialize = "displayName"))]
+    pub display_name: Option<String>,
+    /// State of the VPS (`running`, `stopped`...)
+    pub state: Option<String>,
+    /// Datacenter hosting the VPS
+    pub zone: Option<String>,
+}
+
+impl OvhVps {
+    /// Retrieves a VPS.
+    pub async fn get(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<OvhVps, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/vps/{}", name)).await?;
+        Ok(res)
+    }
+
+    /// Lists the names of all of the VPS of the account.
+    pub async fn list_names(
+        c: &OvhClient,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json("/vps").await?;
+        Ok(res)
+    }
+
+    /// Lists the IP addresses of a VPS.
+    pub async fn ips(
+        c: &OvhClient,
+        name: &str,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let res = c.get_json(&format!("/vps/{}/ips", name)).await?;
+        Ok(res)
+    }
+}
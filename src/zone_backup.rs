@@ -0,0 +1,248 @@
+//! Periodic backups of DNS zones.
+//!
+//! [`ZoneBackup`] exports a set of zones in the BIND format and hands
+//! every zone file to a [`ZoneBackupSink`], such as [`DirectorySink`]
+//! writing timestamped files. In incremental mode, zones whose SOA serial
+//! did not change since the previous run are skipped, so that the backup
+//! can be scheduled often without piling up identical files.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::client::OvhClient;
+use crate::dns_zone::{for_each_zone, OvhDnsZone};
+
+/// Destination of the zone files exported by [`ZoneBackup`].
+pub trait ZoneBackupSink {
+    /// Stores the BIND export of a zone at the given SOA serial.
+    fn write(
+        &mut self,
+        zone: &str,
+        serial: u64,
+        contents: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Any closure taking the zone, its serial and its export can be used as
+/// a sink.
+impl<F> ZoneBackupSink for F
+where
+    F: FnMut(&str, u64, &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>>,
+{
+    fn write(
+        &mut self,
+        zone: &str,
+        serial: u64,
+        contents: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self(zone, serial, contents)
+    }
+}
+
+/// Sink writing every export to its own file in a directory.
+///
+/// Files are named `<zone>-<timestamp>.zone`, the timestamp being the
+/// number of seconds since the Unix epoch at the time of the write.
+#[derive(Debug)]
+pub struct DirectorySink {
+    dir: PathBuf,
+}
+
+impl DirectorySink {
+    /// Creates a sink writing to the given directory, which is created on
+    /// the first write if it does not exist.
+    pub fn new<T>(dir: T) -> Self
+    where
+        T: Into<PathBuf>,
+    {
+        Self { dir: dir.into() }
+    }
+}
+
+impl ZoneBackupSink for DirectorySink {
+    fn write(
+        &mut self,
+        zone: &str,
+        _serial: u64,
+        contents: &str,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        fs::create_dir_all(&self.dir)?;
+        fs::write(
+            self.dir.join(format!("{}-{}.zone", zone, timestamp)),
+            contents,
+        )?;
+        Ok(())
+    }
+}
+
+/// What happened to a zone during a run of [`ZoneBackup::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ZoneBackupStatus {
+    /// The zone was exported and written to the sink
+    Written {
+        /// SOA serial of the exported zone
+        serial: u64,
+    },
+    /// The zone was skipped as its serial did not change since the last
+    /// backup
+    Unchanged {
+        /// SOA serial of the zone
+        serial: u64,
+    },
+    /// The zone could not be exported or written
+    Failed(String),
+}
+
+/// Backup of a set of DNS zones, remembering the serials of the zones
+/// already backed up.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use ovh::client::OvhClient;
+/// use ovh::dns_zone::OvhDnsZone;
+/// use ovh::zone_backup::{DirectorySink, ZoneBackup};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let zones = OvhDnsZone::list_names(&c).await.unwrap();
+///     let mut sink = DirectorySink::new("backups");
+///     let mut backup = ZoneBackup::new().incremental(true);
+///
+///     loop {
+///         for (zone, status) in backup.run(&c, &zones, &mut sink).await {
+///             println!("{}: {:?}", zone, status);
+///         }
+///         c.sleep(Duration::from_secs(3600)).await;
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ZoneBackup {
+    concurrency: usize,
+    incremental: bool,
+    serials: HashMap<String, u64>,
+}
+
+impl Default for ZoneBackup {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            incremental: false,
+            serials: HashMap::new(),
+        }
+    }
+}
+
+impl ZoneBackup {
+    /// Creates a backup exporting 8 zones concurrently, writing every zone
+    /// on every run.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of zones exported concurrently.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Skips the zones whose SOA serial is the one recorded at their last
+    /// successful backup.
+    pub fn incremental(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
+
+    /// Sets the serials of the zones already backed up, for instance as
+    /// saved from [`serials`](Self::serials) by a previous process.
+    pub fn with_serials(mut self, serials: HashMap<String, u64>) -> Self {
+        self.serials = serials;
+        self
+    }
+
+    /// Returns the SOA serial of every zone at its last successful backup.
+    pub fn serials(&self) -> &HashMap<String, u64> {
+        &self.serials
+    }
+
+    /// Exports the given zones and writes them to `sink`.
+    ///
+    /// A zone failing does not stop the others. Results are returned along
+    /// with the name of their zone, in the same order as `zones`.
+    pub async fn run<S, T>(
+        &mut self,
+        c: &OvhClient,
+        zones: &[T],
+        sink: &mut S,
+    ) -> Vec<(String, ZoneBackupStatus)>
+    where
+        S: ZoneBackupSink + ?Sized,
+        T: AsRef<str>,
+    {
+        let serials = &self.serials;
+        let incremental = self.incremental;
+        let exports = for_each_zone(c, zones, self.concurrency, |zone| async move {
+            let serial = OvhDnsZone::soa(c, &zone).await?.serial;
+            if incremental && serials.get(&zone) == Some(&serial) {
+                return Ok((serial, None));
+            }
+            let contents = OvhDnsZone::export(c, &zone).await?;
+            Ok((serial, Some(contents)))
+        })
+        .await;
+
+        let mut res = Vec::new();
+        let mut written = Vec::new();
+        for (zone, export) in exports.results {
+            let status = match export {
+                Ok((serial, None)) => ZoneBackupStatus::Unchanged { serial },
+                Ok((serial, Some(contents))) => match sink.write(&zone, serial, &contents) {
+                    Ok(()) => {
+                        written.push((zone.clone(), serial));
+                        ZoneBackupStatus::Written { serial }
+                    }
+                    Err(e) => ZoneBackupStatus::Failed(e.to_string()),
+                },
+                Err(e) => ZoneBackupStatus::Failed(e.to_string()),
+            };
+            res.push((zone, status));
+        }
+
+        self.serials.extend(written);
+        res
+    }
+}
+
+/// Exports every zone of `zones` once and writes it to `sink`.
+///
+/// This is a shorthand for [`ZoneBackup::run`] with the default settings.
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::zone_backup::{backup_zones, DirectorySink};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let res = backup_zones(&c, &["example.com", "example.org"], &mut DirectorySink::new("backups")).await;
+///     println!("{:?}", res);
+/// }
+/// ```
+pub async fn backup_zones<S, T>(
+    c: &OvhClient,
+    zones: &[T],
+    sink: &mut S,
+) -> Vec<(String, ZoneBackupStatus)>
+where
+    S: ZoneBackupSink + ?Sized,
+    T: AsRef<str>,
+{
+    ZoneBackup::new().run(c, zones, sink).await
+}
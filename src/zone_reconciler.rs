@@ -0,0 +1,481 @@
+//! Continuous reconciliation of DNS zones with a desired state.
+//!
+//! [`ZoneReconciler`] is the building block of controllers in the style of
+//! [external-dns](https://github.com/kubernetes-sigs/external-dns): it
+//! periodically asks a [`RecordSource`] which records a zone should hold,
+//! compares them with the records it owns according to its
+//! [`TxtRegistry`], and applies the difference.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::Duration,
+    time::Instant,
+};
+
+use crate::client::OvhClient;
+use crate::dns_owner::TxtRegistry;
+use crate::dns_record::DnsRecordType;
+use crate::dns_zone::{OvhDnsZone, OvhRrset};
+use crate::plan::{Change, ChangeAction, ResourceKind};
+use crate::rate_limit::RateLimiter;
+
+/// Records a zone should hold for a subdomain and a type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesiredRrset {
+    /// Subdomain of the records, empty for the zone apex
+    pub sub_domain: String,
+    /// Type of the records
+    pub field_type: DnsRecordType,
+    /// Targets of the records
+    pub targets: Vec<String>,
+    /// TTL of the records, or `None` for the default TTL of the zone
+    pub ttl: Option<u32>,
+}
+
+impl DesiredRrset {
+    /// Creates the desired state of a set of records.
+    pub fn new(sub_domain: &str, field_type: DnsRecordType, targets: &[&str]) -> Self {
+        Self {
+            sub_domain: sub_domain.into(),
+            field_type,
+            targets: targets.iter().map(|t| t.to_string()).collect(),
+            ttl: None,
+        }
+    }
+
+    /// Sets the TTL of the records.
+    pub fn with_ttl(mut self, ttl: u32) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+/// Future returned by [`RecordSource::desired`].
+pub type SourceFuture<'a> = Pin<
+    Box<
+        dyn Future<Output = Result<Vec<DesiredRrset>, Box<dyn std::error::Error + Send + Sync>>>
+            + Send
+            + 'a,
+    >,
+>;
+
+/// Source of the records a zone should hold, such as the services of a
+/// Kubernetes cluster.
+///
+/// A fixed list of records is a source:
+///
+/// ```
+/// use ovh::dns_record::DnsRecordType;
+/// use ovh::zone_reconciler::DesiredRrset;
+///
+/// let source = vec![DesiredRrset::new("www", DnsRecordType::A, &["203.0.113.1"])];
+/// ```
+pub trait RecordSource: Send + Sync {
+    /// Returns the records the zone should hold.
+    ///
+    /// Records missing from the result are deleted if they are owned by
+    /// the reconciler, so a source failing to list some of them should
+    /// return an error instead.
+    fn desired<'a>(&'a self, zone: &'a str) -> SourceFuture<'a>;
+}
+
+impl RecordSource for Vec<DesiredRrset> {
+    fn desired<'a>(&'a self, _zone: &'a str) -> SourceFuture<'a> {
+        Box::pin(async move { Ok(self.clone()) })
+    }
+}
+
+impl<T: RecordSource + ?Sized> RecordSource for Arc<T> {
+    fn desired<'a>(&'a self, zone: &'a str) -> SourceFuture<'a> {
+        (**self).desired(zone)
+    }
+}
+
+/// Records the reconciler is allowed to delete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReconcilePolicy {
+    /// Owned records missing from the source are deleted
+    #[default]
+    Sync,
+    /// Records are created and updated, never deleted
+    UpsertOnly,
+}
+
+/// Change to a set of records computed by [`ZoneReconciler::plan`].
+///
+/// Changes convert to the generic [`Change`] of a [`Plan`](crate::plan::Plan):
+///
+/// ```no_run
+/// use ovh::client::OvhClient;
+/// use ovh::dns_owner::TxtRegistry;
+/// use ovh::plan::Plan;
+/// use ovh::zone_reconciler::{DesiredRrset, ZoneReconciler};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let source: Vec<DesiredRrset> = vec![];
+///     let reconciler = ZoneReconciler::new("example.com", TxtRegistry::new("ci"));
+///     let changes = reconciler.plan(&c, &source).await.unwrap();
+///     let plan: Plan = changes.iter().map(Into::into).collect();
+///     println!("{}", serde_json::to_string_pretty(&plan).unwrap());
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconcileChange {
+    /// What is done to the set: created when it does not exist yet,
+    /// updated when its targets or TTL differ, deleted when it is owned
+    /// but no longer desired
+    pub action: ChangeAction,
+    /// Zone of the records
+    pub zone: String,
+    /// Subdomain of the records
+    pub sub_domain: String,
+    /// Type of the records
+    pub field_type: DnsRecordType,
+    /// Targets before the change, sorted
+    pub before: Vec<String>,
+    /// Targets after the change, sorted
+    pub after: Vec<String>,
+    /// TTL after the change
+    pub ttl: Option<u32>,
+}
+
+impl From<&ReconcileChange> for Change {
+    fn from(c: &ReconcileChange) -> Self {
+        let state = |targets: &[String], ttl: Option<u32>| serde_json::json!({ "targets": targets, "ttl": ttl });
+        Change {
+            kind: ResourceKind::DnsRecordSet,
+            resource: format!("{}/{}/{}", c.zone, c.sub_domain, c.field_type),
+            action: c.action,
+            before: match c.action {
+                ChangeAction::Create => None,
+                _ => Some(state(&c.before, None)),
+            },
+            after: match c.action {
+                ChangeAction::Delete => None,
+                _ => Some(state(&c.after, c.ttl)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for ReconcileChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} {} {} {:?} -> {:?}",
+            self.action, self.sub_domain, self.field_type, self.before, self.after
+        )
+    }
+}
+
+/// Outcome of a run of [`ZoneReconciler::reconcile`].
+#[derive(Debug, Default)]
+pub struct ReconcileReport {
+    /// Zone reconciled
+    pub zone: String,
+    /// Changes applied
+    pub applied: Vec<ReconcileChange>,
+    /// Changes that failed, with the error message
+    pub failed: Vec<(ReconcileChange, String)>,
+    /// Error preventing the changes from being computed, such as a failure
+    /// of the source
+    pub error: Option<String>,
+    /// Duration of the run
+    pub elapsed: Duration,
+}
+
+impl ReconcileReport {
+    /// Whether the zone now holds the desired records.
+    pub fn is_success(&self) -> bool {
+        self.failed.is_empty() && self.error.is_none()
+    }
+}
+
+/// Reconciler keeping the records of a zone in line with a
+/// [`RecordSource`].
+///
+/// Only the records marked as owned by its [`TxtRegistry`] are updated or
+/// deleted, so records created by hand or by other tools are left alone.
+/// The zone is refreshed after every run applying changes.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use ovh::client::OvhClient;
+/// use ovh::dns_owner::TxtRegistry;
+/// use ovh::dns_record::DnsRecordType;
+/// use ovh::rate_limit::RateLimiter;
+/// use ovh::zone_reconciler::{DesiredRrset, ZoneReconciler};
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+///     let source = vec![
+///         DesiredRrset::new("www", DnsRecordType::A, &["203.0.113.1", "203.0.113.2"]).with_ttl(300),
+///     ];
+///     ZoneReconciler::new("example.com", TxtRegistry::new("cluster-1"))
+///         .with_interval(Duration::from_secs(60))
+///         .with_rate_limit(RateLimiter::new().per_second(2.0))
+///         .with_observer(|report| println!("{:?}", report))
+///         .run(&c, &source)
+///         .await;
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ZoneReconciler {
+    zone: String,
+    registry: TxtRegistry,
+    interval: Duration,
+    policy: ReconcilePolicy,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    observer: Option<Observer>,
+}
+
+type Observer = Arc<dyn Fn(&ReconcileReport) + Send + Sync>;
+
+impl fmt::Debug for ZoneReconciler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZoneReconciler")
+            .field("zone", &self.zone)
+            .field("registry", &self.registry)
+            .field("interval", &self.interval)
+            .field("policy", &self.policy)
+            .finish()
+    }
+}
+
+impl ZoneReconciler {
+    /// Creates a reconciler of a zone, running every minute.
+    pub fn new(zone: &str, registry: TxtRegistry) -> Self {
+        Self {
+            zone: zone.into(),
+            registry,
+            interval: Duration::from_secs(60),
+            policy: ReconcilePolicy::default(),
+            rate_limiter: None,
+            observer: None,
+        }
+    }
+
+    /// Sets the time waited between two runs of [`run`](Self::run).
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets which records may be deleted.
+    pub fn with_policy(mut self, policy: ReconcilePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Limits the rate at which changes are applied, on top of the rate
+    /// limit of the client.
+    pub fn with_rate_limit(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(Arc::new(limiter));
+        self
+    }
+
+    /// Calls `observer` with the report of every run of
+    /// [`run`](Self::run), for instance to update metrics.
+    pub fn with_observer<F>(mut self, observer: F) -> Self
+    where
+        F: Fn(&ReconcileReport) + Send + Sync + 'static,
+    {
+        self.observer = Some(Arc::new(observer));
+        self
+    }
+
+    /// Computes the changes needed for the zone to hold the records of
+    /// `source`, without applying them.
+    ///
+    /// Subdomains are compared case-insensitively, and targets with the
+    /// normalization rules of
+    /// [`OvhDnsRecord::matches`](crate::dns_record::OvhDnsRecord::matches).
+    pub async fn plan<S>(
+        &self,
+        c: &OvhClient,
+        source: &S,
+    ) -> Result<Vec<ReconcileChange>, Box<dyn std::error::Error + Send + Sync>>
+    where
+        S: RecordSource + ?Sized,
+    {
+        let desired = source
+            .desired(&self.zone)
+            .await
+            .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { e })?;
+        let mut owned: HashMap<(String, DnsRecordType), OvhRrset> = self
+            .registry
+            .owned_rrsets(c, &self.zone)
+            .await?
+            .into_iter()
+            .map(|s| ((s.sub_domain.to_lowercase(), s.field_type), s))
+            .collect();
+
+        let mut changes = Vec::new();
+        for d in desired {
+            let mut after = d.targets.clone();
+            after.sort();
+            after.dedup();
+            let current = owned.remove(&(d.sub_domain.to_lowercase(), d.field_type));
+            let action = match &current {
+                None => ChangeAction::Create,
+                Some(set) => {
+                    let before = normalized_targets(d.field_type, set.targets());
+                    let after = normalized_targets(d.field_type, after.iter().map(|t| t.as_str()));
+                    let ttl_differs = d
+                        .ttl
+                        .is_some_and(|ttl| set.records.iter().any(|r| r.ttl != ttl));
+                    if before != after || ttl_differs {
+                        ChangeAction::Update
+                    } else {
+                        continue;
+                    }
+                }
+            };
+            changes.push(ReconcileChange {
+                action,
+                zone: self.zone.clone(),
+                sub_domain: d.sub_domain,
+                field_type: d.field_type,
+                before: current.as_ref().map(sorted_targets).unwrap_or_default(),
+                after,
+                ttl: d.ttl,
+            });
+        }
+
+        if self.policy == ReconcilePolicy::Sync {
+            let mut stale: Vec<OvhRrset> = owned.into_values().collect();
+            stale.sort_by(|a, b| {
+                (&a.sub_domain, a.field_type.as_str()).cmp(&(&b.sub_domain, b.field_type.as_str()))
+            });
+            changes.extend(stale.into_iter().map(|s| ReconcileChange {
+                action: ChangeAction::Delete,
+                zone: self.zone.clone(),
+                before: sorted_targets(&s),
+                sub_domain: s.sub_domain,
+                field_type: s.field_type,
+                after: Vec::new(),
+                ttl: None,
+            }));
+        }
+
+        Ok(changes)
+    }
+
+    /// Computes and applies the changes needed for the zone to hold the
+    /// records of `source`, then refreshes the zone if anything changed.
+    ///
+    /// A change failing, for instance because the records exist but are
+    /// not owned by the reconciler, does not stop the others.
+    pub async fn reconcile<S>(&self, c: &OvhClient, source: &S) -> ReconcileReport
+    where
+        S: RecordSource + ?Sized,
+    {
+        let start = Instant::now();
+        let mut report = ReconcileReport {
+            zone: self.zone.clone(),
+            ..Default::default()
+        };
+
+        match self.plan(c, source).await {
+            Ok(changes) => {
+                for change in changes {
+                    match self.apply(c, &change).await {
+                        Ok(()) => report.applied.push(change),
+                        Err(e) => report.failed.push((change, e.to_string())),
+                    }
+                }
+                if !report.applied.is_empty() {
+                    if let Err(e) = OvhDnsZone::refresh(c, &self.zone).await {
+                        report.error = Some(format!("refresh: {}", e));
+                    }
+                }
+            }
+            Err(e) => report.error = Some(e.to_string()),
+        }
+
+        report.elapsed = start.elapsed();
+        report
+    }
+
+    /// Reconciles the zone forever, waiting for the interval between two
+    /// runs and passing every report to the observer.
+    ///
+    /// The future never completes: spawn it and abort the task to stop the
+    /// reconciler.
+    ///
+    /// ```no_run
+    /// use ovh::client::OvhClient;
+    /// use ovh::dns_owner::TxtRegistry;
+    /// use ovh::zone_reconciler::{DesiredRrset, ZoneReconciler};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let c = OvhClient::from_conf("ovh.conf").unwrap();
+    ///     let source: Vec<DesiredRrset> = vec![];
+    ///     let reconciler = ZoneReconciler::new("example.com", TxtRegistry::new("ci"));
+    ///     let task = tokio::spawn(async move { reconciler.run(&c, &source).await });
+    ///     tokio::signal::ctrl_c().await.unwrap();
+    ///     task.abort();
+    /// }
+    /// ```
+    pub async fn run<S>(&self, c: &OvhClient, source: &S)
+    where
+        S: RecordSource + ?Sized,
+    {
+        loop {
+            let report = self.reconcile(c, source).await;
+            if let Some(observer) = &self.observer {
+                observer(&report);
+            }
+            c.sleep(self.interval).await;
+        }
+    }
+
+    async fn apply(
+        &self,
+        c: &OvhClient,
+        change: &ReconcileChange,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let _permit = match &self.rate_limiter {
+            Some(l) => l.acquire(c.timer()).await,
+            None => None,
+        };
+        let targets: Vec<&str> = change.after.iter().map(|t| t.as_str()).collect();
+        self.registry
+            .set_rrset(
+                c,
+                &self.zone,
+                &change.sub_domain,
+                change.field_type,
+                &targets,
+                change.ttl,
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+fn normalized_targets<'a>(
+    field_type: DnsRecordType,
+    targets: impl IntoIterator<Item = &'a str>,
+) -> BTreeSet<String> {
+    targets
+        .into_iter()
+        .map(|t| field_type.normalize_target(t))
+        .collect()
+}
+
+fn sorted_targets(set: &OvhRrset) -> Vec<String> {
+    let mut targets: Vec<String> = set.targets().into_iter().map(String::from).collect();
+    targets.sort();
+    targets.dedup();
+    targets
+}
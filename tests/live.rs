@@ -0,0 +1,133 @@
+//! Integration tests against the real API.
+//!
+//! They are skipped unless credentials are given with the `OVH_*`
+//! environment variables read by [`EnvProvider`], along with:
+//!
+//! - `OVH_TEST_ZONE`: a DNS zone dedicated to the tests
+//! - `OVH_TEST_EMAIL_DOMAIN`: a domain whose email redirections may be
+//!   created and deleted
+//!
+//! Every resource created by a test is deleted at its end, whether it
+//! passed or not, and is named with an `ovh-rs-test-` prefix so leftovers
+//! of an interrupted run are easy to spot.
+//!
+//! ```sh
+//! OVH_ENDPOINT=ovh-eu OVH_APPLICATION_KEY=... OVH_APPLICATION_SECRET=... \
+//!     OVH_CONSUMER_KEY=... OVH_TEST_ZONE=test.example.com \
+//!     cargo test --test live -- --test-threads 1
+//! ```
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ovh::client::OvhClient;
+use ovh::dns_record::{DnsRecordType, OvhDnsRecord};
+use ovh::dns_zone::OvhDnsZone;
+use ovh::email_redir::OvhMailRedir;
+use ovh::secret::EnvProvider;
+
+/// Returns a client and the value of `var`, or `None` to skip the test.
+fn setup(var: &str) -> Option<(OvhClient, String)> {
+    let target = match std::env::var(var) {
+        Ok(v) if !v.is_empty() => v,
+        _ => {
+            eprintln!("{} is not set, skipping", var);
+            return None;
+        }
+    };
+    let c = OvhClient::from_provider(&EnvProvider).expect("invalid OVH_* credentials");
+    Some((c, target))
+}
+
+/// Returns a name unique to this run.
+fn unique_name() -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    format!("ovh-rs-test-{}", now.as_millis())
+}
+
+#[tokio::test]
+async fn dns_record_lifecycle() {
+    let (c, zone) = match setup("OVH_TEST_ZONE") {
+        Some(s) => s,
+        None => return,
+    };
+    let sub_domain = unique_name();
+
+    let record = OvhDnsRecord::create(
+        &c,
+        &zone,
+        &sub_domain,
+        DnsRecordType::Txt,
+        "\"ovh-rs\"",
+        Some(60),
+    )
+    .await
+    .expect("creating the record");
+
+    let res = async {
+        let fetched = OvhDnsRecord::get(&c, &zone, record.id)
+            .await?
+            .ok_or("created record not found")?;
+        assert_eq!(fetched.sub_domain, sub_domain);
+        assert_eq!(fetched.ttl, 60);
+
+        let listed = OvhDnsRecord::list_by_sub_domain(&c, &zone, &sub_domain, None).await?;
+        assert!(listed.iter().any(|r| r.id == record.id));
+        Ok::<_, Box<dyn std::error::Error + Send + Sync>>(())
+    }
+    .await;
+
+    let cleanup = async {
+        OvhDnsRecord::delete(&c, &zone, record.id).await?;
+        OvhDnsZone::refresh(&c, &zone).await
+    }
+    .await;
+
+    res.expect("checking the record");
+    cleanup.expect("deleting the record");
+    assert!(OvhDnsRecord::get(&c, &zone, record.id)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn email_redirection_lifecycle() {
+    let (c, domain) = match setup("OVH_TEST_EMAIL_DOMAIN") {
+        Some(s) => s,
+        None => return,
+    };
+    let from = format!("{}@{}", unique_name(), domain);
+    let to = format!("postmaster@{}", domain);
+
+    OvhClient::ensure_success(
+        OvhMailRedir::create(&c, &domain, &from, &to, false)
+            .await
+            .unwrap(),
+    )
+    .await
+    .expect("creating the redirection");
+
+    // The redirection is created by an asynchronous task.
+    let mut found = Vec::new();
+    for _ in 0..12 {
+        found = OvhMailRedir::list(&c, &domain)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|r| r.from.as_deref() == Some(from.as_str()))
+            .collect();
+        if !found.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_secs(5)).await;
+    }
+
+    for r in &found {
+        OvhClient::ensure_success(OvhMailRedir::delete(&c, &domain, r.id).await.unwrap())
+            .await
+            .expect("deleting the redirection");
+    }
+
+    assert_eq!(found.len(), 1, "created redirection not found");
+    assert_eq!(found[0].to.as_deref(), Some(to.as_str()));
+}